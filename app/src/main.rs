@@ -1,9 +1,6 @@
-use std::{
-    io::{self},
-    sync::Arc,
-};
+use std::{env, error::Error, sync::Arc};
 
-use ray_tracing_core::prelude::*;
+use ray_tracing_core::{prelude::*, scene::Scene};
 
 fn generate_sphere_random() -> HittableList {
     let mut world = HittableList::new();
@@ -41,11 +38,18 @@ fn generate_sphere_random() -> HittableList {
     world
 }
 
-fn main() -> Result<(), io::Error> {
+fn main() -> Result<(), Box<dyn Error>> {
+    // Render the scene description file if one is given
+    if let Some(path) = env::args().nth(1) {
+        Scene::from_file(path)?.render()?;
+
+        return Ok(());
+    }
+
     // World
     let mut world = generate_sphere_random();
 
-    let material_ground = Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+    let material_ground = Arc::new(Lambertian::new(Color::GRAY));
     world.add(Arc::new(Sphere::new(
         Point3::with_y(-1000.),
         1000.,
@@ -73,17 +77,8 @@ fn main() -> Result<(), io::Error> {
     )));
 
     // Camera render
-    Camera::builder()
-        .set_aspect_ratio(16. / 9.)
-        .set_image_width(1200)
-        .set_samples_per_pixel(500)
-        .set_max_depth(50)
-        .set_vertical_view_angle(20.)
-        .set_look_from(Point3::new(13., 2., 3.))
-        .set_look_at(Point3::zero())
-        .set_vup(Vec3::with_y(1.))
-        .set_defocus_angle(0.6)
-        .set_focus_distance(10.)
+    Camera::preset_outdoor()
+        .set_tile_size(32)
         .build()
         .render(Arc::new(world))?;
 
@@ -0,0 +1,102 @@
+use std::{
+    fs::{self, File},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::prelude::*;
+
+/// A sequence of frames, each one a scene built from the frame number.
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use ray_tracing_core::{animation::Animation, prelude::*};
+/// // A sphere moving along the X axis, seen by a fixed camera.
+/// let animation = Animation::new(|frame| {
+///     let mut world = HittableList::new();
+///     let center = Point3::new(-1. + 0.1 * frame as f64, 0., -2.);
+///     world.add(Arc::new(Sphere::new(center, 0.5, None)));
+///
+///     (world, Camera::builder().set_image_width(200).build())
+/// });
+///
+/// animation.render_sequence(20, "images/frames").unwrap();
+/// ```
+pub struct Animation<F>
+where
+    F: Fn(u32) -> (HittableList, Camera),
+{
+    scene: F,
+}
+
+impl<F> Animation<F>
+where
+    F: Fn(u32) -> (HittableList, Camera),
+{
+    /// Create a new animation, `scene` returns the world and the (built) camera of a frame.
+    pub fn new(scene: F) -> Self {
+        Self { scene }
+    }
+
+    /// Render the frames `0..frames` into numbered PPM images in `out_dir`.
+    ///
+    /// The directory is created if it doesn't exist, returns the statistics of each frame.
+    pub fn render_sequence<P: AsRef<Path>>(
+        &self,
+        frames: u32,
+        out_dir: P,
+    ) -> Result<Vec<RenderStats>, RenderError> {
+        fs::create_dir_all(&out_dir)?;
+
+        (0..frames)
+            .map(|frame| {
+                let (world, mut camera) = (self.scene)(frame);
+                let file = File::create(frame_path(&out_dir, frame))?;
+
+                camera.render_to(Arc::new(world), file)
+            })
+            .collect()
+    }
+}
+
+/// Returns the path of the image of a frame, like `out_dir/frame_0007.ppm`.
+pub fn frame_path<P: AsRef<Path>>(out_dir: P, frame: u32) -> PathBuf {
+    out_dir.as_ref().join(format!("frame_{frame:04}.ppm"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_sequence_should_write_numbered_frames() {
+        let out_dir = std::env::temp_dir().join("ray_tracing_core_animation_test");
+        let _ = fs::remove_dir_all(&out_dir);
+
+        let animation = Animation::new(|frame| {
+            let camera = Camera::builder()
+                .set_image_width(4 + frame)
+                .set_samples_per_pixel(1)
+                .build();
+
+            (HittableList::new(), camera)
+        });
+        let stats = animation.render_sequence(2, &out_dir).unwrap();
+        assert_eq!(stats.len(), 2);
+
+        for frame in 0..2 {
+            let image = fs::read_to_string(frame_path(&out_dir, frame));
+            let size = 4 + frame;
+
+            assert!(
+                image
+                    .unwrap()
+                    .starts_with(&format!("P3\n{size} {size}\n255\n"))
+            );
+        }
+
+        fs::remove_dir_all(&out_dir).unwrap();
+    }
+}
@@ -1,15 +1,33 @@
+mod error;
+mod estimator;
+mod sampler;
+mod stats;
+
+pub use error::{CameraError, RenderError};
+pub use estimator::PixelEstimator;
+pub use sampler::Sampler;
+pub use stats::RenderStats;
+
 use std::{
+    f64::consts::PI,
+    fmt::Debug,
     io::{self, BufWriter, Write},
+    ops::Range,
     sync::{
         Arc,
-        atomic::{AtomicU32, Ordering},
+        atomic::{AtomicBool, AtomicU32, Ordering},
     },
-    time::Instant,
+    time::{Duration, Instant},
 };
 
 use rayon::prelude::*;
 
-use crate::{color, common, prelude::*};
+use crate::{
+    color::{self, Rgb},
+    common,
+    prelude::*,
+};
+use stats::RenderCounters;
 
 #[derive(Debug)]
 pub struct Camera {
@@ -27,6 +45,12 @@ pub struct Camera {
     max_depth: u32,
     /// Vertical view angle (field of view)
     vfov: Degrees,
+    /// Horizontal view angle, replaces `vfov` when building if set
+    hfov: Option<Degrees>,
+    /// Radial distortion coefficients `(k1, k2)` of the lens
+    lens_distortion: (f64, f64),
+    /// How the pixels are mapped to ray directions
+    projection_mode: ProjectionMode,
     /// Point camera is looking from
     look_from: Point3,
     /// Point camera is looking at
@@ -37,6 +61,8 @@ pub struct Camera {
     defocus_angle: Degrees,
     /// Distance from camera lookfrom point to plane of perfect focus
     focus_dist: f64,
+    /// Number of aperture blades, the defocus disk is a regular polygon if at least 3
+    aperture_blades: u32,
     /// Camera center
     center: Point3,
     /// Location of pixel 0, 0
@@ -51,6 +77,66 @@ pub struct Camera {
     defocus_disk_v: Vec3,
     /// Camera frame basis vectors
     basis: CameraBasis,
+    /// Edge length of the square tiles rendered in parallel (0 means per scanline)
+    tile_size: u32,
+    /// What the rendered image shows
+    render_mode: RenderMode,
+    /// Transfer function of the output image
+    color_space: color::ColorSpace,
+    /// Flag to stop rendering, shared with the caller
+    cancel: Option<Arc<AtomicBool>>,
+    /// Render on multiple threads
+    parallel: bool,
+    /// Region `(x0, y0, x1, y1)` of the image to render, the rest is left black
+    crop_window: Option<(u32, u32, u32, u32)>,
+    /// Point lights sampled at every diffuse hit
+    point_lights: Vec<PointLight>,
+    /// How the sub-pixel and lens positions of the samples are picked
+    sampler: Sampler,
+    /// Render progressive passes until this duration elapsed, instead of a sample count
+    time_budget: Option<Duration>,
+    /// Maximum luminance of a single sample
+    sample_clamp: Option<f64>,
+    /// How the samples of a pixel are reduced to its color
+    pixel_estimator: PixelEstimator,
+    /// Print the progress every this many scanlines, tiles or passes
+    progress_interval: u32,
+    /// Print nothing to stderr while rendering
+    quiet: bool,
+    /// Color of the rays missing the scene, the sky gradient if `None`
+    background: Option<Background>,
+}
+
+/// What the camera renders for each pixel.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum RenderMode {
+    /// Full path tracing with materials and lighting
+    #[default]
+    Shaded,
+    /// Color every primary hit by its outward surface normal, ignoring materials and
+    /// lighting, misses are black
+    Normals,
+    /// Color every primary hit by its surface coordinates as `(u, v, 0)`, misses are black
+    Uv,
+    /// Shade every primary hit by the fraction of `samples` cosine-weighted rays over its
+    /// hemisphere escaping within `radius`, ignoring materials and lighting, misses are
+    /// white. Zero `samples` is rejected when building the camera
+    AmbientOcclusion { samples: u32, radius: f64 },
+}
+
+/// How the camera maps the pixels to ray directions.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum ProjectionMode {
+    /// Planar viewport seen through a (thin) lens
+    #[default]
+    Perspective,
+    /// 360° panorama with a 2:1 image, for VR and panorama viewers
+    ///
+    /// The longitude grows from -180° at the left edge to 180° at the right edge, 0° being
+    /// the look-at direction and 90° the camera right. The latitude goes from 90° (the
+    /// up vector) at the top edge to -90° at the bottom edge. The aspect ratio, view
+    /// angle, defocus blur and lens distortion are ignored.
+    Equirectangular,
 }
 
 /// Camera frame basis vectors
@@ -64,29 +150,75 @@ struct CameraBasis {
     w: Vec3,
 }
 
-// Return the color for a given scene ray
-fn ray_color<H: Hittable>(ray: Ray, depth: u32, world: Arc<H>) -> Color {
-    // If we've exceeded the ray bounce limit, no more light is gathered.
-    if depth == 0 {
-        return Color::zero();
-    }
+/// Custom background, called for the rays missing the scene
+struct Background(Box<dyn Fn(&Ray) -> Color + Send + Sync>);
 
-    if let Some(hit) = world.hit(&ray, Interval::new(0.001, f64::INFINITY))
-        && let Some(material) = &hit.material
-    {
-        if let Some((attenuation, scattered)) = material.scatter(&ray, &hit) {
-            return attenuation * ray_color(scattered, depth - 1, world.clone());
-        } else {
-            return Color::zero();
-        }
+impl Debug for Background {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Background(..)")
     }
+}
 
-    let direction = ray.direction.to_unit();
+// Return the color of the sky for a ray missing the scene
+fn background(ray: &Ray) -> Color {
+    let direction = ray.direction_unit();
     let a = 0.5 * (direction.y + 1.);
 
     (1. - a) * Color::one() + a * Color::new(0.5, 0.7, 1.)
 }
 
+// Return the color encoding the outward normal of the first hit of the ray
+fn normal_color<H: Hittable>(ray: Ray, world: &H) -> Color {
+    match world.hit(&ray, Interval::new(0.001, f64::INFINITY)) {
+        Some(hit) => {
+            let outward_normal = if hit.front_face {
+                hit.normal
+            } else {
+                -hit.normal
+            };
+
+            0.5 * (outward_normal + Color::one())
+        }
+        None => Color::zero(),
+    }
+}
+
+// Return the color encoding the surface coordinates of the first hit of the ray
+fn uv_color<H: Hittable>(ray: Ray, world: &H) -> Color {
+    match world.hit(&ray, Interval::new(0.001, f64::INFINITY)) {
+        Some(HitRecord { uv: (u, v), .. }) => Color::with_xy(u, v),
+        None => Color::zero(),
+    }
+}
+
+// Return the ambient occlusion of the first hit of the ray, `rays` counts the rays traced
+// and `shadow_rays` the occlusion rays among them
+fn ambient_occlusion<H: Hittable>(
+    ray: Ray,
+    world: &H,
+    samples: u32,
+    radius: f64,
+    rays: &mut u64,
+    shadow_rays: &mut u64,
+) -> Color {
+    *rays += 1;
+    let Some(hit) = world.hit(&ray, Interval::new(0.001, f64::INFINITY)) else {
+        return Color::one();
+    };
+
+    let onb = Onb::from_normal(&hit.normal);
+    let escaped = (0..samples)
+        .filter(|_| {
+            let direction = onb.local(Vec3::random_cosine_direction());
+            !world.hit_any(&Ray::new(hit.p, direction), Interval::new(0.001, radius))
+        })
+        .count();
+    *rays += samples as u64;
+    *shadow_rays += samples as u64;
+
+    Color::with_isotropic(escaped as f64 / samples as f64)
+}
+
 fn sample_square() -> Vec3 {
     // Returns the vector to a random point in the [-.5,-.5]-[+.5,+.5] unit square.
     Vec3::with_xy(common::random() - 0.5, common::random() - 0.5)
@@ -121,11 +253,15 @@ impl Camera {
             pixel_samples_scale: 0.,
             max_depth: 10,
             vfov: Degrees(90.),
+            hfov: None,
+            lens_distortion: (0., 0.),
+            projection_mode: ProjectionMode::default(),
             look_from: Point3::zero(),
             look_at: Point3::with_z(-1.),
             vup: Vec3::with_y(1.),
             defocus_angle: Degrees(0.),
             focus_dist: 10.,
+            aperture_blades: 0,
             center: Point3::zero(),
             pixel00_loc: Point3::zero(),
             pixel_delta_u: Vec3::zero(),
@@ -133,9 +269,61 @@ impl Camera {
             defocus_disk_u: Vec3::zero(),
             defocus_disk_v: Vec3::zero(),
             basis: CameraBasis::default(),
+            tile_size: 0,
+            render_mode: RenderMode::default(),
+            color_space: color::ColorSpace::default(),
+            cancel: None,
+            parallel: true,
+            crop_window: None,
+            point_lights: Vec::new(),
+            sampler: Sampler::default(),
+            time_budget: None,
+            sample_clamp: None,
+            pixel_estimator: PixelEstimator::default(),
+            progress_interval: 1,
+            quiet: false,
+            background: None,
         }
     }
 
+    /// A builder set up for a Cornell box: a 555 units wide box with its open side
+    /// facing -z, spanning `(0, 0, 0)` to `(555, 555, 555)`.
+    ///
+    /// The camera looks into the box through the open side, with a 40° view angle and
+    /// a square image. Every setting can still be overridden.
+    pub fn preset_cornell() -> Self {
+        Self::builder()
+            .set_aspect_ratio(1.)
+            .set_image_width(600)
+            .set_samples_per_pixel(200)
+            .set_max_depth(50)
+            .set_vertical_view_angle(40.)
+            .set_look_from(Point3::new(278., 278., -800.))
+            .set_look_at(Point3::new(278., 278., 0.))
+            .set_vup(Vec3::with_y(1.))
+            .set_defocus_angle(0.)
+    }
+
+    /// A builder set up for an outdoor scene: objects of about unit size around the
+    /// origin, standing on a large ground, lit by the sky.
+    ///
+    /// The camera looks at the origin from slightly above with a 20° view angle, a wide
+    /// image and a shallow depth of field focused 10 units away. Every setting can still
+    /// be overridden.
+    pub fn preset_outdoor() -> Self {
+        Self::builder()
+            .set_aspect_ratio(16. / 9.)
+            .set_image_width(1200)
+            .set_samples_per_pixel(500)
+            .set_max_depth(50)
+            .set_vertical_view_angle(20.)
+            .set_look_from(Point3::new(13., 2., 3.))
+            .set_look_at(Point3::zero())
+            .set_vup(Vec3::with_y(1.))
+            .set_defocus_angle(0.6)
+            .set_focus_distance(10.)
+    }
+
     /// Set the aspect ratio of the camera.
     pub fn set_aspect_ratio(mut self, aspect_ratio: f64) -> Self {
         self.aspect_ratio = aspect_ratio;
@@ -155,14 +343,56 @@ impl Camera {
     }
 
     /// Set the maximum depth of the camera.
+    ///
+    /// # Note
+    ///
+    /// A zero depth traces no ray into the scene and renders a black image when shaded.
     pub fn set_max_depth(mut self, max_depth: u32) -> Self {
         self.max_depth = max_depth;
         self
     }
 
     /// Set the vertical view angle of the camera.
+    ///
+    /// # Note
+    ///
+    /// The last view angle set wins, this overrides `set_horizontal_view_angle()`.
     pub fn set_vertical_view_angle(mut self, vfov: f64) -> Self {
         self.vfov = Degrees(vfov);
+        self.hfov = None;
+        self
+    }
+
+    /// Set the horizontal view angle of the camera, the vertical one is derived from it
+    /// and the aspect ratio of the image when building.
+    ///
+    /// # Note
+    ///
+    /// The last view angle set wins, this overrides `set_vertical_view_angle()`.
+    pub fn set_horizontal_view_angle(mut self, hfov: f64) -> Self {
+        self.hfov = Some(Degrees(hfov));
+        self
+    }
+
+    /// Set the radial distortion of the lens, which magnifies the image by
+    /// `1 + k1 * r^2 + k2 * r^4` at the distance `r` from its center, `r` being 1 at the
+    /// corners.
+    ///
+    /// Positive coefficients give a pincushion distortion, negative ones a barrel
+    /// distortion, both zero (the default) is the undistorted pinhole image.
+    ///
+    /// # Note
+    ///
+    /// The magnification must stay positive over the image, e.g. `k1 > -1` alone, the
+    /// camera fails to build otherwise.
+    pub fn set_lens_distortion(mut self, k1: f64, k2: f64) -> Self {
+        self.lens_distortion = (k1, k2);
+        self
+    }
+
+    /// Set the projection mode of the camera, perspective by default.
+    pub fn set_projection_mode(mut self, projection_mode: ProjectionMode) -> Self {
+        self.projection_mode = projection_mode;
         self
     }
 
@@ -196,11 +426,211 @@ impl Camera {
         self
     }
 
+    /// Set the number of aperture blades of the camera.
+    ///
+    /// With `n >= 3` blades the lens samples a regular n-gon instead of a disk, so the
+    /// out-of-focus highlights take the polygonal shape of a real lens aperture.
+    /// Values below 3 keep the circular disk.
+    pub fn set_aperture_blades(mut self, aperture_blades: u32) -> Self {
+        self.aperture_blades = aperture_blades;
+        self
+    }
+
+    /// Set the tile size of the camera.
+    ///
+    /// The image is divided into `tile_size × tile_size` blocks which are rendered in
+    /// parallel and reassembled, this balances the work better than scanlines when the
+    /// scene complexity varies across the image.
+    ///
+    /// # Note
+    ///
+    /// `0` (the default) keeps rendering per scanline.
+    pub fn set_tile_size(mut self, tile_size: u32) -> Self {
+        self.tile_size = tile_size;
+        self
+    }
+
+    /// Set the render mode of the camera.
+    pub fn set_render_mode(mut self, render_mode: RenderMode) -> Self {
+        self.render_mode = render_mode;
+        self
+    }
+
+    /// Render ambient occlusion with `samples` rays per hit, occluders farther than
+    /// `radius` are ignored. Same as `set_render_mode(RenderMode::AmbientOcclusion { .. })`.
+    pub fn set_ambient_occlusion(self, samples: u32, radius: f64) -> Self {
+        self.set_render_mode(RenderMode::AmbientOcclusion { samples, radius })
+    }
+
+    /// Set the gamma of the output image, 1 produces linear output.
+    ///
+    /// `gamma` must be a positive number.
+    pub fn set_gamma(mut self, gamma: f64) -> Self {
+        self.color_space = color::ColorSpace::Gamma(gamma);
+        self
+    }
+
+    /// Set the color space (transfer function) of the output image.
+    pub fn set_color_space(mut self, color_space: color::ColorSpace) -> Self {
+        self.color_space = color_space;
+        self
+    }
+
+    /// Set the flag to cancel the rendering.
+    ///
+    /// Once the flag is set to `true`, no new scanline (or tile) is started and `render`
+    /// writes the partial image, pixels not rendered yet are left black.
+    pub fn set_cancel_flag(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    /// Set whether the camera renders on multiple threads.
+    ///
+    /// When `false` the image is rendered scanline after scanline on the calling thread,
+    /// which makes a single pixel easy to step through in a debugger. The tile size is
+    /// ignored in that case.
+    pub fn set_parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    /// Set the sampler picking the sub-pixel and lens positions of the samples.
+    ///
+    /// # Note
+    ///
+    /// With aperture blades the lens positions are always random.
+    pub fn set_sampler(mut self, sampler: Sampler) -> Self {
+        self.sampler = sampler;
+        self
+    }
+
+    /// Set the color of the rays missing the scene, replacing the default sky gradient.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ray_tracing_core::prelude::*;
+    /// // A sun disc in a dark blue sky
+    /// let sun = Vec3::new(1., 1., 0.).to_unit();
+    /// let camera = Camera::builder()
+    ///     .set_background(move |ray| {
+    ///         if ray.direction_unit().dot(&sun) > 0.999 {
+    ///             Color::new(20., 18., 15.)
+    ///         } else {
+    ///             Color::new(0.05, 0.1, 0.3)
+    ///         }
+    ///     })
+    ///     .build();
+    /// ```
+    pub fn set_background<F>(mut self, background: F) -> Self
+    where
+        F: Fn(&Ray) -> Color + Send + Sync + 'static,
+    {
+        self.background = Some(Background(Box::new(background)));
+        self
+    }
+
+    /// Render for about `budget` instead of a fixed number of samples per pixel.
+    ///
+    /// The image is rendered in passes of one sample per pixel, the time is checked
+    /// between passes and the rendering stops once the budget is exhausted. The result
+    /// is the average of the completed passes, at least one pass is always rendered.
+    /// The samples per pixel setting and the tile size are ignored.
+    pub fn set_time_budget(mut self, budget: Duration) -> Self {
+        self.time_budget = Some(budget);
+        self
+    }
+
+    /// Clamp the luminance of every sample to `max_luminance` before averaging, which
+    /// removes the isolated bright pixels ("fireflies") of rare high-energy paths.
+    ///
+    /// # Note
+    ///
+    /// The clamping loses energy, so it introduces bias: bright highlights and caustics
+    /// are rendered darker than they should be. No clamping by default.
+    ///
+    /// `max_luminance` must be a positive number.
+    pub fn set_sample_clamp(mut self, max_luminance: f64) -> Self {
+        self.sample_clamp = Some(max_luminance);
+        self
+    }
+
+    /// Set how the samples of a pixel are reduced to its color, the mean by default.
+    ///
+    /// # Note
+    ///
+    /// `Median` and `TrimmedMean` keep all the samples of a pixel in memory while it's
+    /// estimated, and are ignored when rendering with a time budget.
+    pub fn set_pixel_estimator(mut self, estimator: PixelEstimator) -> Self {
+        self.pixel_estimator = estimator;
+        self
+    }
+
+    /// Print the progress every `every_n_rows` scanlines instead of every one, which
+    /// also applies to tiles and progressive passes. It must not be zero, use
+    /// `set_quiet()` to print nothing.
+    pub fn set_progress_interval(mut self, every_n_rows: u32) -> Self {
+        self.progress_interval = every_n_rows;
+        self
+    }
+
+    /// Print nothing to stderr while rendering, neither the progress nor the final
+    /// statistics, which suits non-TTY output such as CI logs.
+    pub fn set_quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+
+    /// Set the crop window of the camera, only the pixels `(i, j)` with `x0 <= i < x1`
+    /// and `y0 <= j < y1` are rendered.
+    ///
+    /// The output keeps the full image dimensions, the pixels outside the window are
+    /// left black. An empty window renders a black image.
+    ///
+    /// # Panics
+    ///
+    /// `build()` panics if the window exceeds the image bounds.
+    pub fn set_crop_window(mut self, x0: u32, y0: u32, x1: u32, y1: u32) -> Self {
+        self.crop_window = Some((x0, y0, x1, y1));
+        self
+    }
+
+    /// Add a point light at `position`, sampled with a shadow ray at every diffuse hit.
+    ///
+    /// The light reaching a surface is `color * intensity / distance²`, weighted by the
+    /// cosine to the surface normal. Point lights are added on top of the background
+    /// lighting and cast sharp shadows.
+    pub fn add_point_light(mut self, position: Point3, color: Color, intensity: f64) -> Self {
+        self.point_lights
+            .push(PointLight::new(position, color, intensity));
+        self
+    }
+
     /// Build the camera at last.
     ///
     /// * Initialize the camera.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a parameter is invalid, see `try_build()`.
     pub fn build(self) -> Self {
-        self.initialize()
+        self.try_build().unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Like `build()` but returns an error instead of panicking on invalid parameters.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ray_tracing_core::prelude::*;
+    /// let camera = Camera::builder().set_samples_per_pixel(0).try_build();
+    /// assert_eq!(camera.unwrap_err(), CameraError::ZeroSamplesPerPixel);
+    /// ```
+    pub fn try_build(self) -> Result<Self, CameraError> {
+        self.validate()?;
+
+        Ok(self.initialize())
     }
 
     /// Render the scene with the given world.
@@ -208,82 +638,622 @@ impl Camera {
     /// # Note
     ///
     /// You should call `build()` before calling this method.
-    pub fn render<H: Hittable>(&mut self, world: Arc<H>) -> Result<(), io::Error> {
-        // Writer
+    pub fn render<H: Hittable>(&mut self, world: Arc<H>) -> Result<RenderStats, RenderError> {
         let stdout = io::stdout();
-        let mut writer = BufWriter::new(stdout.lock());
 
-        // Remaining lines
-        let remaining_lines = AtomicU32::new(self.image_height);
+        self.render_to(world, stdout.lock())
+    }
 
-        // Start timer
-        let now = Instant::now();
+    /// Render the scene with the given world, writing the PPM image into `writer`.
+    ///
+    /// # Note
+    ///
+    /// You should call `build()` before calling this method.
+    pub fn render_to<H: Hittable, W: Write>(
+        &mut self,
+        world: Arc<H>,
+        writer: W,
+    ) -> Result<RenderStats, RenderError> {
+        // Writer
+        let mut writer = BufWriter::new(writer);
+
+        let (hdr, stats) = self.render_to_hdr(world.as_ref())?;
 
-        // Render
         writer.write_all(b"P3\n")?;
         writer.write_all(format!("{} {}\n", self.image_width, self.image_height).as_bytes())?;
         writer.write_all(b"255\n")?;
 
-        let world = world.clone();
-        let rows = (0..self.image_height)
+        for pixel in hdr.to_rgb_in(self.color_space) {
+            writer.write_all(format!("{pixel}\n").as_bytes())?;
+        }
+        writer.flush()?;
+
+        Ok(stats)
+    }
+
+    /// Render the scene with the given world, writing the linear colors into the OpenEXR
+    /// image `path` (32-bit float RGB, no transfer function nor clamping applied).
+    ///
+    /// # Note
+    ///
+    /// You should call `build()` before calling this method.
+    #[cfg(feature = "exr")]
+    pub fn render_exr<H: Hittable, P: AsRef<std::path::Path>>(
+        &mut self,
+        world: Arc<H>,
+        path: P,
+    ) -> Result<RenderStats, RenderError> {
+        let (hdr, stats) = self.render_to_hdr(world.as_ref())?;
+
+        let (width, height) = (hdr.width() as usize, hdr.height() as usize);
+        exr::prelude::write_rgb_file(path, width, height, |x, y| {
+            let pixel = hdr.pixel(x as u32, y as u32);
+
+            (pixel.x as f32, pixel.y as f32, pixel.z as f32)
+        })?;
+
+        Ok(stats)
+    }
+
+    /// Render the scene with the given world into `buffer`, in scanline order.
+    ///
+    /// The buffer is reused as is, which avoids allocating a new image for each frame
+    /// when rendering repeatedly, e.g. for an interactive preview. Its length must be
+    /// `image_width * image_height`.
+    ///
+    /// # Note
+    ///
+    /// You should call `build()` before calling this method.
+    pub fn render_into<H: Hittable>(
+        &self,
+        world: &H,
+        buffer: &mut [Rgb],
+    ) -> Result<RenderStats, RenderError> {
+        self.check_buffer(buffer.len())?;
+
+        let (stats, _) = self.render_pixels(world, buffer, |pixel| {
+            color::translate_color_in(pixel.unwrap_or_default(), self.color_space).into()
+        });
+
+        Ok(stats)
+    }
+
+    // Check the camera is built and the buffer has one pixel per image pixel.
+    fn check_buffer(&self, len: usize) -> Result<(), RenderError> {
+        if self.image_height == 0 {
+            return Err(RenderError::NotBuilt);
+        }
+
+        let expected = (self.image_width * self.image_height) as usize;
+        if len != expected {
+            return Err(RenderError::BufferSize {
+                expected,
+                actual: len,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Render the scene with the given world into a buffer of linear colors, before any
+    /// transfer function or quantization.
+    ///
+    /// The pixels outside the crop window, or skipped when the rendering is cancelled,
+    /// are black with a sample count of 0.
+    ///
+    /// # Note
+    ///
+    /// You should call `build()` before calling this method.
+    pub fn render_to_hdr<H: Hittable>(
+        &self,
+        world: &H,
+    ) -> Result<(HdrBuffer, RenderStats), RenderError> {
+        let mut pixels = vec![None; (self.image_width * self.image_height) as usize];
+        self.check_buffer(pixels.len())?;
+        let (stats, samples) = self.render_pixels(world, &mut pixels, |pixel| pixel);
+
+        let sample_counts = pixels
+            .iter()
+            .map(|pixel| if pixel.is_some() { samples } else { 0 })
+            .collect();
+        let pixels = pixels.into_iter().map(Option::unwrap_or_default).collect();
+        let hdr =
+            HdrBuffer::from_pixels(self.image_width, self.image_height, pixels, sample_counts);
+
+        Ok((hdr, stats))
+    }
+
+    // Render all pixels into `pixels` in scanline order, `shade` converts the linear colors,
+    // `None` for the pixels left out (outside the crop window or cancelled).
+    // Returns the statistics and the number of samples per rendered pixel.
+    fn render_pixels<H, T, F>(&self, world: &H, pixels: &mut [T], shade: F) -> (RenderStats, u32)
+    where
+        H: Hittable,
+        T: Copy + Send,
+        F: Fn(Option<Color>) -> T + Sync,
+    {
+        // Start timer
+        let now = Instant::now();
+
+        // Render
+        let counters = RenderCounters::default();
+        let mut samples = self.samples_per_pixel;
+        if let Some(budget) = self.time_budget {
+            samples = self.render_progressive(world, &counters, pixels, &shade, now + budget);
+        } else if !self.parallel {
+            self.render_sequential(world, &counters, pixels, &shade);
+        } else if self.tile_size == 0 {
+            self.render_scanlines(world, &counters, pixels, &shade);
+        } else {
+            self.render_tiles(world, &counters, pixels, &shade);
+        }
+
+        // End timer
+        let elapsed = now.elapsed();
+        let stats = counters.into_stats(elapsed);
+        if !self.quiet {
+            eprint!("\r\x1B[K");
+            if self.is_cancelled() {
+                eprintln!("\nCancelled. Elapsed time: {}ms", elapsed.as_millis());
+            } else {
+                eprintln!("\nDone. Elapsed time: {}ms", elapsed.as_millis());
+            }
+            eprintln!("{stats}");
+        }
+
+        (stats, samples)
+    }
+
+    // Render passes of one sample per pixel until `deadline`, at least one, and average them.
+    // Returns the number of passes.
+    fn render_progressive<H, T, F>(
+        &self,
+        world: &H,
+        counters: &RenderCounters,
+        pixels: &mut [T],
+        shade: &F,
+        deadline: Instant,
+    ) -> u32
+    where
+        H: Hittable,
+        T: Send,
+        F: Fn(Option<Color>) -> T + Sync,
+    {
+        let width = self.image_width as usize;
+        let mut sums = vec![Color::zero(); pixels.len()];
+        let mut passes = 0;
+
+        loop {
+            let render_row = |(j, row): (usize, &mut [Color])| {
+                for (i, sum) in row.iter_mut().enumerate() {
+                    *sum +=
+                        self.sum_samples(i as u32, j as u32, passes..passes + 1, world, counters);
+                }
+            };
+
+            if self.parallel {
+                sums.par_chunks_mut(width).enumerate().for_each(render_row); // rayon parallelize
+            } else {
+                sums.chunks_mut(width).enumerate().for_each(render_row);
+            }
+            passes += 1;
+
+            self.report_progress("Passes", passes, counters);
+
+            if self.is_cancelled() || Instant::now() >= deadline {
+                break;
+            }
+        }
+
+        for ((index, pixel), sum) in (0..).zip(pixels.iter_mut()).zip(sums) {
+            let (i, j) = (index % self.image_width, index / self.image_width);
+            *pixel = shade(self.in_crop_window(i, j).then(|| sum / passes));
+        }
+
+        passes
+    }
+
+    // Render the image row by row on the calling thread.
+    fn render_sequential<H, T, F>(
+        &self,
+        world: &H,
+        counters: &RenderCounters,
+        pixels: &mut [T],
+        shade: &F,
+    ) where
+        H: Hittable,
+        F: Fn(Option<Color>) -> T,
+    {
+        for (j, row) in (0..).zip(pixels.chunks_mut(self.image_width as usize)) {
+            if self.is_cancelled() {
+                row.iter_mut().for_each(|pixel| *pixel = shade(None));
+                continue;
+            }
+
+            for (i, pixel) in (0..).zip(row.iter_mut()) {
+                *pixel = shade(self.render_pixel(i, j, world, counters));
+            }
+
+            self.report_progress("Scanlines remaining", self.image_height - j - 1, counters);
+        }
+    }
+
+    // Render the image row by row, each scanline is a unit of parallel work.
+    fn render_scanlines<H, T, F>(
+        &self,
+        world: &H,
+        counters: &RenderCounters,
+        pixels: &mut [T],
+        shade: &F,
+    ) where
+        H: Hittable,
+        T: Send,
+        F: Fn(Option<Color>) -> T + Sync,
+    {
+        // Remaining lines
+        let remaining_lines = AtomicU32::new(self.image_height);
+
+        pixels
+            .par_chunks_mut(self.image_width as usize) // rayon parallelize
+            .enumerate()
+            .for_each(|(j, row)| {
+                if self.is_cancelled() {
+                    row.iter_mut().for_each(|pixel| *pixel = shade(None));
+                    return;
+                }
+
+                row.par_iter_mut() // rayon parallelize
+                    .enumerate()
+                    .for_each(|(i, pixel)| {
+                        *pixel = shade(self.render_pixel(i as u32, j as u32, world, counters));
+                    });
+
+                let remaining = remaining_lines.fetch_sub(1, Ordering::Relaxed);
+                self.report_progress("Scanlines remaining", remaining - 1, counters);
+            });
+    }
+
+    // Render the image tile by tile, then copy the tiles into scanline order.
+    fn render_tiles<H, T, F>(
+        &self,
+        world: &H,
+        counters: &RenderCounters,
+        pixels: &mut [T],
+        shade: &F,
+    ) where
+        H: Hittable,
+        T: Copy + Send,
+        F: Fn(Option<Color>) -> T + Sync,
+    {
+        let tile_size = self.tile_size;
+        let tiles_x = self.image_width.div_ceil(tile_size);
+        let tiles_y = self.image_height.div_ceil(tile_size);
+
+        // Remaining tiles
+        let remaining_tiles = AtomicU32::new(tiles_x * tiles_y);
+
+        let tiles = (0..tiles_x * tiles_y)
             .into_par_iter() // rayon parallelize
-            .map(|j| {
-                let row = (0..self.image_width)
-                    .into_par_iter() // rayon parallelize
-                    .map(|i| {
-                        let pixel_color: Color = (0..self.samples_per_pixel)
-                            .map(|_| {
-                                let ray = self.sample_ray(i, j);
-
-                                ray_color(ray, self.max_depth, world.clone())
-                            })
-                            .sum();
-
-                        color::translate_color(self.pixel_samples_scale * pixel_color)
+            .map(|index| {
+                let x0 = (index % tiles_x) * tile_size;
+                let y0 = (index / tiles_x) * tile_size;
+                let x1 = (x0 + tile_size).min(self.image_width);
+                let y1 = (y0 + tile_size).min(self.image_height);
+
+                let cancelled = self.is_cancelled();
+                let tile = (y0..y1)
+                    .flat_map(|j| (x0..x1).map(move |i| (i, j)))
+                    .map(|(i, j)| {
+                        if cancelled {
+                            shade(None)
+                        } else {
+                            shade(self.render_pixel(i, j, world, counters))
+                        }
                     })
                     .collect::<Vec<_>>();
 
-                let remaining = remaining_lines.fetch_sub(1, Ordering::Relaxed);
-                eprint!("\r\x1B[KScanlines remaining: {}", remaining - 1);
-
-                let mut row_bytes = Vec::with_capacity(row.len() * 10);
-                for (r, g, b) in &row {
-                    row_bytes.extend_from_slice(format!("{r} {g} {b}\n").as_bytes());
+                if !cancelled {
+                    let remaining = remaining_tiles.fetch_sub(1, Ordering::Relaxed);
+                    self.report_progress("Tiles remaining", remaining - 1, counters);
                 }
 
-                row_bytes
+                (x0, y0, x1 - x0, tile)
             })
             .collect::<Vec<_>>();
 
-        for row in &rows {
-            writer.write_all(row)?;
+        for (x0, y0, width, tile) in tiles {
+            for (row, tile_row) in tile.chunks(width as usize).enumerate() {
+                let start = ((y0 + row as u32) * self.image_width + x0) as usize;
+                pixels[start..start + tile_row.len()].copy_from_slice(tile_row);
+            }
         }
+    }
 
-        // End timer
-        eprint!("\r\x1B[K");
-        let elapsed = now.elapsed();
-        eprintln!("\nDone. Elapsed time: {}ms", elapsed.as_millis());
+    // Print the progress `count` of the render, every `progress_interval` counts.
+    fn report_progress(&self, label: &str, count: u32, counters: &RenderCounters) {
+        if !self.should_report(count) {
+            return;
+        }
 
-        Ok(())
+        eprint!(
+            "\r\x1B[K{label}: {count} ({:.2} Mrays/s)",
+            counters.rays_per_second() / 1e6
+        );
     }
 
-    fn initialize(mut self) -> Self {
-        if self.aspect_ratio.abs() < f64::EPSILON {
-            panic!("Aspect ratio cannot be zero");
+    // Check if the progress `count` is printed.
+    fn should_report(&self, count: u32) -> bool {
+        !self.quiet && count.is_multiple_of(self.progress_interval)
+    }
+
+    // Check if the caller asked to stop rendering.
+    fn is_cancelled(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+    }
+
+    // Return the color for a given scene ray, `rays` counts the rays traced into the scene
+    // and `shadow_rays` the ones towards the point lights among them
+    fn ray_color<H: Hittable>(
+        &self,
+        mut ray: Ray,
+        world: &H,
+        rays: &mut u64,
+        shadow_rays: &mut u64,
+    ) -> Color {
+        // Product of the attenuations along the path so far
+        let mut throughput = Color::one();
+        // Light gathered directly from the point lights
+        let mut radiance = Color::zero();
+
+        for _ in 0..self.max_depth {
+            *rays += 1;
+
+            if let Some(hit) = world.hit(&ray, Interval::new(0.001, f64::INFINITY))
+                && let Some(material) = &hit.material
+            {
+                if let Some((attenuation, scattered, diffuse)) = material.scatter_lobe(&ray, &hit) {
+                    if diffuse {
+                        for light in &self.point_lights {
+                            *rays += 1;
+                            *shadow_rays += 1;
+                            if let Some(light_color) = light.illuminate(&hit, world) {
+                                radiance += throughput * attenuation * light_color;
+                            }
+                        }
+                    }
+
+                    throughput *= attenuation;
+                    // Keep the color channel split by a dispersive material.
+                    ray = match scattered.channel {
+                        Some(_) => scattered,
+                        None => scattered.set_channel(ray.channel),
+                    };
+                    continue;
+                } else {
+                    return radiance;
+                }
+            }
+
+            return radiance + throughput * self.background_color(&ray);
+        }
+
+        // If we've exceeded the ray bounce limit, no more light is gathered.
+        radiance
+    }
+
+    // Return the color of a ray missing the scene.
+    fn background_color(&self, ray: &Ray) -> Color {
+        match &self.background {
+            Some(Background(background)) => background(ray),
+            None => background(ray),
+        }
+    }
+
+    // Estimate the pixel at location i, j from all its random samples, as a linear color.
+    // Returns `None` outside the crop window.
+    fn render_pixel<H: Hittable>(
+        &self,
+        i: u32,
+        j: u32,
+        world: &H,
+        counters: &RenderCounters,
+    ) -> Option<Color> {
+        if !self.in_crop_window(i, j) {
+            return None;
+        }
+
+        let samples = 0..self.samples_per_pixel;
+
+        Some(match self.pixel_estimator {
+            PixelEstimator::Mean => {
+                self.pixel_samples_scale * self.sum_samples(i, j, samples, world, counters)
+            }
+            estimator => self.reduce_samples(i, j, samples, world, counters, |samples| {
+                estimator.estimate(&samples.collect::<Vec<_>>())
+            }),
+        })
+    }
+
+    // Check if the pixel at location i, j is rendered, true without a crop window.
+    fn in_crop_window(&self, i: u32, j: u32) -> bool {
+        self.crop_window
+            .is_none_or(|(x0, y0, x1, y1)| (x0..x1).contains(&i) && (y0..y1).contains(&j))
+    }
+
+    // Scale a sample down to the maximum luminance, if any.
+    fn clamp_sample(&self, sample: Color) -> Color {
+        match self.sample_clamp {
+            Some(max_luminance) if sample.luminance() > max_luminance => {
+                max_luminance / sample.luminance() * sample
+            }
+            _ => sample,
+        }
+    }
+
+    // Sum the samples with indices in `samples` of the pixel at location i, j, as linear colors.
+    fn sum_samples<H: Hittable>(
+        &self,
+        i: u32,
+        j: u32,
+        samples: Range<u32>,
+        world: &H,
+        counters: &RenderCounters,
+    ) -> Color {
+        self.reduce_samples(i, j, samples, world, counters, |samples| samples.sum())
+    }
+
+    // Reduce the samples with indices in `samples` of the pixel at location i, j with
+    // `reduce`, black outside the crop window.
+    fn reduce_samples<H, R>(
+        &self,
+        i: u32,
+        j: u32,
+        samples: Range<u32>,
+        world: &H,
+        counters: &RenderCounters,
+        reduce: R,
+    ) -> Color
+    where
+        H: Hittable,
+        R: FnOnce(&mut dyn Iterator<Item = Color>) -> Color,
+    {
+        if !self.in_crop_window(i, j) {
+            return Color::zero();
+        }
+
+        let (mut rays, mut shadow_rays) = (0, 0);
+        let count = samples.len() as u64;
+        let rotation = self.sampler.pixel_rotation(i, j);
+        let pixel_color = reduce(&mut samples.map(|s| {
+            let ray = self.sample_ray(i, j, s, &rotation);
+
+            let sample = match self.render_mode {
+                RenderMode::Shaded => self.ray_color(ray, world, &mut rays, &mut shadow_rays),
+                RenderMode::Normals => {
+                    rays += 1;
+                    normal_color(ray, world)
+                }
+                RenderMode::Uv => {
+                    rays += 1;
+                    uv_color(ray, world)
+                }
+                RenderMode::AmbientOcclusion { samples, radius } => {
+                    ambient_occlusion(ray, world, samples, radius, &mut rays, &mut shadow_rays)
+                }
+            };
+
+            self.clamp_sample(sample)
+        }));
+        counters.add(count, rays, shadow_rays);
+
+        pixel_color
+    }
+
+    // Check the parameters set by the user.
+    fn validate(&self) -> Result<(), CameraError> {
+        if !(self.aspect_ratio > 0. && self.aspect_ratio.is_finite()) {
+            return Err(CameraError::InvalidAspectRatio(self.aspect_ratio));
         }
 
         if self.image_width == 0 {
-            panic!("Image width cannot be zero");
+            return Err(CameraError::ZeroImageWidth);
+        }
+
+        if self.samples_per_pixel == 0 {
+            return Err(CameraError::ZeroSamplesPerPixel);
+        }
+
+        let fov = **self.hfov.as_ref().unwrap_or(&self.vfov);
+        if !(fov > 0. && fov < 180.) {
+            return Err(CameraError::InvalidViewAngle(fov));
+        }
+
+        if let Some(max_luminance) = self.sample_clamp
+            && !(max_luminance > 0. && max_luminance.is_finite())
+        {
+            return Err(CameraError::InvalidSampleClamp(max_luminance));
+        }
+
+        if !self.lens_magnification_is_positive() {
+            let (k1, k2) = self.lens_distortion;
+            return Err(CameraError::InvalidLensDistortion(k1, k2));
+        }
+
+        if let RenderMode::AmbientOcclusion { samples: 0, .. } = self.render_mode {
+            return Err(CameraError::ZeroOcclusionSamples);
+        }
+
+        if let color::ColorSpace::Gamma(gamma) = self.color_space
+            && !(gamma > 0. && gamma.is_finite())
+        {
+            return Err(CameraError::InvalidGamma(gamma));
+        }
+
+        if self.progress_interval == 0 {
+            return Err(CameraError::ZeroProgressInterval);
+        }
+
+        if !(self.focus_dist > 0. && self.focus_dist.is_finite()) {
+            return Err(CameraError::InvalidFocusDistance(self.focus_dist));
+        }
+
+        if self.vup.cross(&(self.look_from - self.look_at)).near_zero() {
+            return Err(CameraError::DegenerateView);
+        }
+
+        if let Some((_, _, x1, y1)) = self.crop_window
+            && (x1 > self.image_width || y1 > self.compute_image_height())
+        {
+            return Err(CameraError::CropWindowOutOfBounds);
         }
 
-        // Calculate the image height, and ensure that it's at least 1.
-        self.image_height = ((self.image_width as f64 / self.aspect_ratio) as u32).max(1);
+        Ok(())
+    }
+
+    // Check the lens magnification `1 + k1 * s + k2 * s^2` is positive for `s = r^2` in
+    // [0, 1], at the corners or at the vertex of the parabola in between.
+    fn lens_magnification_is_positive(&self) -> bool {
+        let (k1, k2) = self.lens_distortion;
+        let magnification = |s: f64| 1. + k1 * s + k2 * s * s;
+
+        let vertex = -k1 / (2. * k2);
+        let minimum = if k2 > 0. && (0. ..1.).contains(&vertex) {
+            magnification(vertex)
+        } else {
+            magnification(1.)
+        };
+
+        minimum > 0.
+    }
+
+    // Calculate the image height, and ensure that it's at least 1.
+    fn compute_image_height(&self) -> u32 {
+        let aspect_ratio = match self.projection_mode {
+            ProjectionMode::Perspective => self.aspect_ratio,
+            ProjectionMode::Equirectangular => 2.,
+        };
+
+        ((self.image_width as f64 / aspect_ratio) as u32).max(1)
+    }
+
+    fn initialize(mut self) -> Self {
+        self.image_height = self.compute_image_height();
 
         self.pixel_samples_scale = 1. / self.samples_per_pixel as f64;
 
         // Camera center
         self.center = self.look_from;
 
+        // Derive the vertical view angle from the horizontal one, with the same aspect
+        // ratio as the viewport.
+        if let Some(hfov) = &self.hfov {
+            let aspect_ratio = self.image_width as f64 / self.image_height as f64;
+            let h = (hfov.to_radians() / 2.).tan() / aspect_ratio;
+            self.vfov = Degrees((2. * h.atan()).to_degrees());
+        }
+
         // Determine viewport dimensions.
         let theta = self.vfov.to_radians();
         let h = (theta / 2.).tan();
@@ -318,29 +1288,620 @@ impl Camera {
 
     // Construct a camera ray originating from the origin and directed
     // at randomly sampled point around the pixel location i, j.
-    fn sample_ray(&self, i: u32, j: u32) -> Ray {
+    fn sample_ray(&self, i: u32, j: u32, s: u32, rotation: &[f64; 4]) -> Ray {
         // Construct a camera ray originating from the defocus disk and directed at a randomly
-        // sampled point around the pixel location i, j.
+        // sampled point around the pixel location i, j. `s` is the index of the sample.
+
+        let (offset, lens) = match self.sampler {
+            Sampler::Random => (sample_square(), None),
+            Sampler::Halton => {
+                let [x, y, u, v] = sampler::halton_sample(s, rotation);
+                (Vec3::with_xy(x - 0.5, y - 0.5), Some((u, v)))
+            }
+        };
+        if self.projection_mode == ProjectionMode::Equirectangular {
+            let direction = self.panorama_direction(i as f64 + offset.x, j as f64 + offset.y);
+            return Ray::new(self.center, direction);
+        }
 
-        let offset = sample_square();
-        let pixel_sample = self.pixel00_loc
-            + (i as f64 + offset.x) * self.pixel_delta_u
-            + (j as f64 + offset.y) * self.pixel_delta_v;
+        let (x, y) = self.distort(i as f64 + offset.x, j as f64 + offset.y);
+        let pixel_sample = self.pixel00_loc + x * self.pixel_delta_u + y * self.pixel_delta_v;
 
         let ray_origin = if *self.defocus_angle <= 0. {
             self.center
         } else {
-            self.defocus_disk_sample()
+            self.defocus_disk_sample(lens)
         };
         let ray_direction = pixel_sample - ray_origin;
 
         Ray::new(ray_origin, ray_direction)
     }
 
-    fn defocus_disk_sample(&self) -> Point3 {
-        // Returns a random point in the camera defocus disk.
-        let p = Vec3::random_in_unit_disk();
+    // Direction of the image position x, y (in pixels, from the center of the pixel 0, 0)
+    // in an equirectangular panorama.
+    fn panorama_direction(&self, x: f64, y: f64) -> Vec3 {
+        let longitude = ((x + 0.5) / self.image_width as f64 - 0.5) * 2. * PI;
+        let latitude = (0.5 - (y + 0.5) / self.image_height as f64) * PI;
+
+        let horizontal = longitude.sin() * self.basis.u - longitude.cos() * self.basis.w;
+
+        latitude.cos() * horizontal + latitude.sin() * self.basis.v
+    }
+
+    // Move the image position x, y (in pixels, from the center of the pixel 0, 0) to the
+    // position seen through the distorting lens.
+    fn distort(&self, x: f64, y: f64) -> (f64, f64) {
+        let (k1, k2) = self.lens_distortion;
+        if k1 == 0. && k2 == 0. {
+            return (x, y);
+        }
+
+        let (width, height) = (self.image_width as f64, self.image_height as f64);
+        let (center_x, center_y) = ((width - 1.) / 2., (height - 1.) / 2.);
+        let (dx, dy) = (x - center_x, y - center_y);
+
+        let r2 = (dx * dx + dy * dy) / ((width * width + height * height) / 4.);
+        let magnification = 1. + k1 * r2 + k2 * r2 * r2;
+
+        (center_x + dx / magnification, center_y + dy / magnification)
+    }
+
+    fn defocus_disk_sample(&self, lens: Option<(f64, f64)>) -> Point3 {
+        // Returns a point in the camera defocus disk, at `lens` in the unit square if given,
+        // otherwise a random one.
+        let p = if self.aperture_blades >= 3 {
+            Vec3::random_in_unit_polygon(self.aperture_blades)
+        } else if let Some((u, v)) = lens {
+            sampler::square_to_unit_disk(u, v)
+        } else {
+            Vec3::random_in_unit_disk()
+        };
 
         self.center + p.x * self.defocus_disk_u + p.y * self.defocus_disk_v
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The recursive formulation `ray_color` used to have, kept as the reference.
+    fn ray_color_recursive<H: Hittable>(ray: Ray, depth: u32, world: &H) -> Color {
+        if depth == 0 {
+            return Color::zero();
+        }
+
+        if let Some(hit) = world.hit(&ray, Interval::new(0.001, f64::INFINITY))
+            && let Some(material) = &hit.material
+        {
+            if let Some((attenuation, scattered)) = material.scatter(&ray, &hit) {
+                return attenuation * ray_color_recursive(scattered, depth - 1, world);
+            } else {
+                return Color::zero();
+            }
+        }
+
+        background(&ray)
+    }
+
+    #[test]
+    fn ray_color_should_match_recursive_version() {
+        // Perfect mirrors only, so the paths are deterministic.
+        let mut world = HittableList::new();
+        let mirror = Arc::new(Metal::new(Color::new(0.8, 0.6, 0.2), 0.));
+        world.add(Arc::new(Sphere::new(
+            Point3::new(-0.6, 0., -1.),
+            0.5,
+            Some(mirror.clone()),
+        )));
+        world.add(Arc::new(Sphere::new(
+            Point3::new(0.6, 0., -1.),
+            0.5,
+            Some(mirror),
+        )));
+        world.add(Arc::new(Sphere::new(Point3::new(0., 0.5, -3.), 0.5, None)));
+
+        for direction in [
+            Vec3::new(0., 0., -1.),
+            Vec3::new(-0.1, 0.05, -1.),
+            Vec3::new(0.3, 0.1, -1.),
+            Vec3::new(0., 0.5, -3.),
+            Vec3::new(0., 1., 0.),
+        ] {
+            for depth in [0, 1, 2, 10] {
+                let camera = Camera::builder().set_max_depth(depth).build();
+                let ray = || Ray::new(Point3::zero(), direction);
+
+                assert!(
+                    camera
+                        .ray_color(ray(), &world, &mut 0, &mut 0)
+                        .approx_eq(&ray_color_recursive(ray(), depth, &world), None)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn point_light_should_light_a_diffuse_mix() {
+        let diffuse: Arc<dyn Material> = Arc::new(Lambertian::new(Color::with_isotropic(0.5)));
+        let mirror: Arc<dyn Material> = Arc::new(Metal::new(Color::one(), 0.));
+        let camera = Camera::builder()
+            .set_max_depth(1)
+            .set_background(|_| Color::zero())
+            .add_point_light(Point3::with_y(5.), Color::one(), 16.)
+            .build();
+        let down = || Ray::new(Point3::with_y(2.), Vec3::with_y(-1.));
+
+        let color = |material| {
+            let mut world = HittableList::new();
+            world.add(Arc::new(Sphere::new(Point3::zero(), 1., Some(material))));
+            camera.ray_color(down(), &world, &mut 0, &mut 0)
+        };
+
+        // The diffuse lobe is lit: albedo 0.5 times 16 / 4² at normal incidence
+        let lit = color(Arc::new(Mix::new(diffuse.clone(), mirror.clone(), 0.)));
+        assert!(lit.approx_eq(&Color::with_isotropic(0.5), None));
+        // The mirror lobe isn't
+        assert_eq!(
+            color(Arc::new(Mix::new(diffuse, mirror, 1.))),
+            Color::zero()
+        );
+    }
+
+    #[test]
+    fn point_light_should_not_count_as_a_bounce() {
+        let mut world = HittableList::new();
+        let diffuse = Arc::new(Lambertian::new(Color::with_isotropic(0.5)));
+        world.add(Arc::new(Sphere::new(Point3::zero(), 1., Some(diffuse))));
+        let render = |camera: Camera| {
+            let camera = camera
+                .set_image_width(8)
+                .set_samples_per_pixel(1)
+                .set_max_depth(1)
+                .set_look_from(Point3::with_z(3.))
+                .set_look_at(Point3::zero())
+                .set_quiet(true)
+                .build();
+
+            camera.render_to_hdr(&world).unwrap().1
+        };
+
+        let unlit = render(Camera::builder());
+        let lit = render(Camera::builder().add_point_light(Point3::with_z(5.), Color::one(), 1.));
+
+        assert_eq!(unlit.shadow_rays, 0);
+        assert!(lit.shadow_rays > 0);
+        assert_eq!(lit.total_rays, unlit.total_rays + lit.shadow_rays);
+        assert_eq!(lit.average_bounces(), unlit.average_bounces());
+    }
+
+    #[test]
+    fn normal_color_should_encode_the_outward_normal() {
+        let mut world = HittableList::new();
+        world.add(Arc::new(Sphere::new(Point3::zero(), 1., None)));
+
+        // Head-on, the outward normal is +z
+        let front = Ray::new(Point3::with_z(5.), Vec3::with_z(-1.));
+        assert!(normal_color(front, &world).approx_eq(&Color::new(0.5, 0.5, 1.), None));
+
+        // From the inside, the normal still points outwards
+        let inside = Ray::new(Point3::zero(), Vec3::with_x(1.));
+        assert!(normal_color(inside, &world).approx_eq(&Color::new(1., 0.5, 0.5), None));
+
+        // Misses are black
+        let miss = Ray::new(Point3::with_z(5.), Vec3::with_z(1.));
+        assert_eq!(normal_color(miss, &world), Color::zero());
+    }
+
+    #[test]
+    fn ambient_occlusion_should_darken_occluded_hits() {
+        let mut world = HittableList::new();
+        world.add(Arc::new(Sphere::new(Point3::zero(), 1., None)));
+        let down = || Ray::new(Point3::with_y(2.), Vec3::with_y(-1.));
+
+        // Nothing around the sphere
+        assert_eq!(
+            ambient_occlusion(down(), &world, 16, 10., &mut 0, &mut 0),
+            Color::one()
+        );
+
+        // Enclosed in a larger sphere, within the radius or not
+        world.add(Arc::new(Sphere::new(Point3::zero(), 3., None)));
+        let (mut rays, mut shadow_rays) = (0, 0);
+        assert_eq!(
+            ambient_occlusion(down(), &world, 16, 10., &mut rays, &mut shadow_rays),
+            Color::zero()
+        );
+        assert_eq!((rays, shadow_rays), (17, 16));
+        assert_eq!(
+            ambient_occlusion(down(), &world, 16, 0.5, &mut 0, &mut 0),
+            Color::one()
+        );
+
+        // Misses are white
+        let up = Ray::new(Point3::with_x(5.), Vec3::with_y(1.));
+        assert_eq!(
+            ambient_occlusion(up, &world, 16, 1., &mut 0, &mut 0),
+            Color::one()
+        );
+    }
+
+    #[test]
+    fn set_background_should_color_missing_rays() {
+        let camera = Camera::builder()
+            .set_background(|ray| Color::with_isotropic(ray.direction_unit().y.max(0.)))
+            .build();
+        let world = HittableList::new();
+
+        let up = camera.ray_color(
+            Ray::new(Point3::zero(), Vec3::with_y(2.)),
+            &world,
+            &mut 0,
+            &mut 0,
+        );
+        let down = camera.ray_color(
+            Ray::new(Point3::zero(), Vec3::with_y(-2.)),
+            &world,
+            &mut 0,
+            &mut 0,
+        );
+
+        assert_eq!(up, Color::one());
+        assert_eq!(down, Color::zero());
+    }
+
+    #[test]
+    fn crop_window_should_leave_outside_pixels_black() {
+        let camera = Camera::builder()
+            .set_image_width(10)
+            .set_samples_per_pixel(1)
+            .set_crop_window(2, 2, 5, 5)
+            .build();
+        let world = HittableList::new();
+        let counters = RenderCounters::default();
+
+        assert_eq!(camera.render_pixel(1, 3, &world, &counters), None);
+        assert_eq!(camera.render_pixel(5, 3, &world, &counters), None);
+        assert_ne!(
+            camera.render_pixel(2, 4, &world, &counters),
+            Some(Color::zero())
+        );
+
+        // No sample is counted outside the crop window
+        let (hdr, _) = camera.set_quiet(true).render_to_hdr(&world).unwrap();
+        assert_eq!(hdr.sample_count(1, 3), 0);
+        assert_eq!(hdr.pixel(1, 3), Color::zero());
+        assert_eq!(hdr.sample_count(2, 4), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Crop window exceeds the image bounds")]
+    fn crop_window_should_be_within_image() {
+        Camera::builder()
+            .set_image_width(10)
+            .set_crop_window(0, 0, 11, 5)
+            .build();
+    }
+
+    #[test]
+    fn render_into_should_fill_the_buffer() {
+        let camera = Camera::builder()
+            .set_image_width(8)
+            .set_samples_per_pixel(1)
+            .set_tile_size(3)
+            .build();
+        let mut buffer = vec![Rgb::default(); 64];
+
+        let stats = camera
+            .render_into(&HittableList::new(), &mut buffer)
+            .unwrap();
+
+        assert_eq!(stats.primary_rays, 64);
+        // Only the sky is visible.
+        assert!(
+            buffer
+                .iter()
+                .all(|pixel| pixel.b >= pixel.g && pixel.g >= pixel.r && pixel.r > 0)
+        );
+    }
+
+    #[test]
+    fn tiled_render_should_match_scanline_render() {
+        let mut world = HittableList::new();
+        world.add(Arc::new(Sphere::new(Point3::zero(), 1., None)));
+        let render = |tile_size| {
+            let camera = Camera::builder()
+                .set_image_width(10)
+                .set_samples_per_pixel(4)
+                .set_look_from(Point3::with_z(3.))
+                .set_look_at(Point3::zero())
+                .set_render_mode(RenderMode::Normals)
+                .set_sampler(Sampler::Halton)
+                .set_tile_size(tile_size)
+                .set_quiet(true)
+                .build();
+
+            camera.render_to_hdr(&world).unwrap()
+        };
+
+        // The tiles don't divide the image evenly
+        let (scanline, scanline_stats) = render(0);
+        let (tiled, tiled_stats) = render(3);
+
+        assert_eq!(tiled, scanline);
+        assert_eq!(tiled_stats.primary_rays, scanline_stats.primary_rays);
+        assert_ne!(scanline.pixel(5, 5), Color::zero());
+    }
+
+    #[test]
+    fn cancelled_render_should_leave_the_image_black() {
+        let cancel = Arc::new(AtomicBool::new(true));
+        let render = |camera: Camera| {
+            let camera = camera
+                .set_image_width(8)
+                .set_samples_per_pixel(1)
+                .set_cancel_flag(cancel.clone())
+                .set_quiet(true)
+                .build();
+            let mut buffer = vec![Rgb::new(1, 1, 1); 64];
+            let stats = camera
+                .render_into(&HittableList::new(), &mut buffer)
+                .unwrap();
+            let (hdr, _) = camera.render_to_hdr(&HittableList::new()).unwrap();
+
+            (buffer, stats, hdr)
+        };
+
+        // Sequential, scanlines and tiles
+        for camera in [
+            Camera::builder().set_parallel(false),
+            Camera::builder(),
+            Camera::builder().set_tile_size(3),
+        ] {
+            let (buffer, stats, hdr) = render(camera);
+            assert!(buffer.iter().all(|pixel| *pixel == Rgb::default()));
+            assert_eq!(hdr.sample_count(3, 3), 0);
+            assert_eq!(
+                (stats.pixels, stats.primary_rays, stats.total_rays),
+                (0, 0, 0)
+            );
+        }
+    }
+
+    #[test]
+    fn render_into_should_work_with_zero_max_depth() {
+        let camera = Camera::builder()
+            .set_image_width(4)
+            .set_samples_per_pixel(1)
+            .set_max_depth(0)
+            .build();
+        let mut buffer = vec![Rgb::new(1, 1, 1); 16];
+
+        let stats = camera
+            .render_into(&HittableList::new(), &mut buffer)
+            .unwrap();
+
+        assert_eq!((stats.primary_rays, stats.total_rays), (16, 0));
+        assert_eq!(stats.average_bounces(), 0.);
+        assert!(stats.to_string().contains("average bounces: 0.00"));
+        assert!(buffer.iter().all(|pixel| *pixel == Rgb::default()));
+    }
+
+    #[test]
+    fn render_into_should_check_the_buffer() {
+        let world = HittableList::new();
+        let camera = Camera::builder().set_image_width(8);
+        assert!(matches!(
+            camera.render_into(&world, &mut [Rgb::default(); 64]),
+            Err(RenderError::NotBuilt)
+        ));
+
+        let camera = camera.build();
+        assert!(matches!(
+            camera.render_into(&world, &mut [Rgb::default(); 10]),
+            Err(RenderError::BufferSize {
+                expected: 64,
+                actual: 10
+            })
+        ));
+    }
+
+    #[test]
+    fn time_budget_should_render_whole_passes() {
+        let world = HittableList::new();
+        let mut buffer = vec![Rgb::default(); 16];
+
+        // At least one pass
+        let camera = Camera::builder()
+            .set_image_width(4)
+            .set_time_budget(Duration::ZERO)
+            .build();
+        let stats = camera.render_into(&world, &mut buffer).unwrap();
+        assert_eq!(stats.primary_rays, 16);
+        assert!(buffer.iter().all(|pixel| pixel.b > 0));
+
+        let camera = Camera::builder()
+            .set_image_width(4)
+            .set_time_budget(Duration::from_millis(20))
+            .build();
+        let stats = camera.render_into(&world, &mut buffer).unwrap();
+        assert!(stats.primary_rays > 16);
+        assert_eq!(stats.primary_rays % 16, 0);
+        assert!(stats.elapsed >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn sample_clamp_should_limit_the_luminance() {
+        let world = HittableList::new();
+        let counters = RenderCounters::default();
+        let camera = Camera::builder()
+            .set_background(|_| Color::new(10., 20., 5.))
+            .set_sample_clamp(2.);
+
+        let pixel = camera
+            .build()
+            .render_pixel(0, 0, &world, &counters)
+            .unwrap();
+
+        assert!(common::relative_eq(pixel.luminance(), 2., None));
+        // The hue is kept.
+        assert!(common::relative_eq(pixel.y / pixel.x, 2., None));
+    }
+
+    #[test]
+    fn report_progress_should_follow_the_interval() {
+        let camera = Camera::builder().set_progress_interval(4).build();
+        assert!(camera.should_report(8));
+        assert!(!camera.should_report(6));
+        // The last scanline or tile
+        assert!(camera.should_report(0));
+
+        let camera = Camera::builder().set_quiet(true).build();
+        assert!(!camera.should_report(0));
+        assert!(!camera.should_report(1));
+    }
+
+    #[test]
+    fn try_build_should_reject_invalid_parameters() {
+        let error = |camera: Camera| camera.try_build().unwrap_err();
+
+        assert!(Camera::builder().try_build().is_ok());
+        assert_eq!(
+            error(Camera::builder().set_aspect_ratio(-1.)),
+            CameraError::InvalidAspectRatio(-1.)
+        );
+        assert_eq!(
+            error(Camera::builder().set_image_width(0)),
+            CameraError::ZeroImageWidth
+        );
+        assert_eq!(
+            error(Camera::builder().set_sample_clamp(-1.)),
+            CameraError::InvalidSampleClamp(-1.)
+        );
+        assert!(matches!(
+            error(Camera::builder().set_sample_clamp(f64::NAN)),
+            CameraError::InvalidSampleClamp(_)
+        ));
+        assert_eq!(
+            error(Camera::builder().set_lens_distortion(-1., 0.)),
+            CameraError::InvalidLensDistortion(-1., 0.)
+        );
+        // Positive at the corners, but not in between
+        assert!(matches!(
+            error(Camera::builder().set_lens_distortion(-3., 2.2)),
+            CameraError::InvalidLensDistortion(..)
+        ));
+        assert!(
+            Camera::builder()
+                .set_lens_distortion(-0.5, 0.1)
+                .try_build()
+                .is_ok()
+        );
+        assert_eq!(
+            error(Camera::builder().set_ambient_occlusion(0, 1.)),
+            CameraError::ZeroOcclusionSamples
+        );
+        assert_eq!(
+            error(Camera::builder().set_gamma(0.)),
+            CameraError::InvalidGamma(0.)
+        );
+        assert!(matches!(
+            error(Camera::builder().set_color_space(color::ColorSpace::Gamma(f64::NAN))),
+            CameraError::InvalidGamma(_)
+        ));
+        assert_eq!(
+            error(Camera::builder().set_progress_interval(0)),
+            CameraError::ZeroProgressInterval
+        );
+        assert_eq!(
+            error(Camera::builder().set_vertical_view_angle(f64::NAN)).to_string(),
+            "View angle must be in (0, 180) degrees, but got NaN"
+        );
+        assert_eq!(
+            error(Camera::builder().set_focus_distance(0.)),
+            CameraError::InvalidFocusDistance(0.)
+        );
+        assert_eq!(
+            error(Camera::builder().set_look_at(Point3::zero())),
+            CameraError::DegenerateView
+        );
+    }
+
+    #[test]
+    fn equirectangular_projection_should_cover_the_sphere() {
+        let camera = Camera::builder()
+            .set_image_width(360)
+            .set_aspect_ratio(1.)
+            .set_projection_mode(ProjectionMode::Equirectangular)
+            .set_look_from(Point3::zero())
+            .set_look_at(Point3::with_z(-1.))
+            .build();
+        assert_eq!(camera.image_height, 180);
+
+        let direction = |x, y| camera.panorama_direction(x, y);
+        assert!((direction(179.5, 89.5) - Vec3::with_z(-1.)).length() < 1e-12);
+        assert!((direction(269.5, 89.5) - Vec3::with_x(1.)).length() < 1e-12);
+        assert!((direction(-0.5, 89.5) - Vec3::with_z(1.)).length() < 1e-12);
+        assert!((direction(179.5, -0.5) - Vec3::with_y(1.)).length() < 1e-12);
+    }
+
+    #[test]
+    fn lens_distortion_should_warp_radially() {
+        let camera = |k1| {
+            Camera::builder()
+                .set_image_width(101)
+                .set_aspect_ratio(1.)
+                .set_lens_distortion(k1, 0.)
+                .build()
+        };
+
+        // Undistorted positions are kept exactly
+        assert_eq!(camera(0.).distort(0.1, 99.7), (0.1, 99.7));
+        // The center is fixed, the corners move inwards with a pincushion distortion,
+        // outwards with a barrel one
+        assert_eq!(camera(0.5).distort(50., 50.), (50., 50.));
+        let (x, y) = camera(0.5).distort(0., 0.);
+        assert!(x > 0. && x == y);
+        assert!(camera(-0.5).distort(0., 0.).0 < 0.);
+    }
+
+    #[test]
+    fn horizontal_view_angle_should_set_the_vertical_one() {
+        let camera = Camera::builder()
+            .set_image_width(200)
+            .set_aspect_ratio(2.)
+            .set_horizontal_view_angle(90.)
+            .build();
+        // tan(vfov / 2) = tan(hfov / 2) / aspect ratio
+        assert!(common::relative_eq(
+            *camera.vfov,
+            2. * 0.5f64.atan().to_degrees(),
+            None
+        ));
+
+        // The last view angle set wins
+        let camera = Camera::builder()
+            .set_horizontal_view_angle(90.)
+            .set_vertical_view_angle(40.)
+            .build();
+        assert_eq!(*camera.vfov, 40.);
+    }
+
+    #[cfg(feature = "exr")]
+    #[test]
+    fn render_exr_should_write_openexr_image() {
+        let path = std::env::temp_dir().join("ray_tracing_core_render_exr_test.exr");
+
+        Camera::builder()
+            .set_image_width(4)
+            .set_samples_per_pixel(1)
+            .build()
+            .render_exr(Arc::new(HittableList::new()), &path)
+            .unwrap();
+
+        let image = std::fs::read(&path).unwrap();
+        assert!(image.starts_with(&[0x76, 0x2f, 0x31, 0x01]));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,122 @@
+use std::{error::Error, fmt::Display, io};
+
+/// Error raised when building a camera with invalid parameters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CameraError {
+    /// The aspect ratio is not a positive number
+    InvalidAspectRatio(f64),
+    /// The image width is zero
+    ZeroImageWidth,
+    /// The number of samples per pixel is zero
+    ZeroSamplesPerPixel,
+    /// The vertical or horizontal view angle is not in (0, 180) degrees
+    InvalidViewAngle(f64),
+    /// The focus distance is not a positive number
+    InvalidFocusDistance(f64),
+    /// The look-from and look-at points coincide, or the up vector is parallel to the
+    /// view direction
+    DegenerateView,
+    /// The crop window exceeds the image bounds
+    CropWindowOutOfBounds,
+    /// The progress interval is zero
+    ZeroProgressInterval,
+    /// The maximum luminance of a sample is not a positive number
+    InvalidSampleClamp(f64),
+    /// The lens distortion coefficients `(k1, k2)` don't keep the magnification
+    /// positive over the image
+    InvalidLensDistortion(f64, f64),
+    /// The number of ambient occlusion rays per hit is zero
+    ZeroOcclusionSamples,
+    /// The gamma of the output image is not a positive number
+    InvalidGamma(f64),
+}
+
+impl Display for CameraError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidAspectRatio(ratio) => {
+                write!(f, "Aspect ratio must be positive, but got {ratio}")
+            }
+            Self::ZeroImageWidth => write!(f, "Image width cannot be zero"),
+            Self::ZeroSamplesPerPixel => write!(f, "Samples per pixel cannot be zero"),
+            Self::InvalidViewAngle(vfov) => {
+                write!(f, "View angle must be in (0, 180) degrees, but got {vfov}")
+            }
+            Self::InvalidFocusDistance(dist) => {
+                write!(f, "Focus distance must be positive, but got {dist}")
+            }
+            Self::DegenerateView => write!(f, "View direction and up vector are degenerate"),
+            Self::CropWindowOutOfBounds => write!(f, "Crop window exceeds the image bounds"),
+            Self::ZeroProgressInterval => write!(f, "Progress interval cannot be zero"),
+            Self::InvalidSampleClamp(max) => {
+                write!(f, "Sample clamp must be positive, but got {max}")
+            }
+            Self::ZeroOcclusionSamples => {
+                write!(f, "Ambient occlusion samples cannot be zero")
+            }
+            Self::InvalidGamma(gamma) => write!(f, "Gamma must be positive, but got {gamma}"),
+            Self::InvalidLensDistortion(k1, k2) => write!(
+                f,
+                "Lens distortion ({k1}, {k2}) must keep the magnification positive"
+            ),
+        }
+    }
+}
+
+impl Error for CameraError {}
+
+/// Error raised when rendering an image.
+///
+/// The variants depend on the enabled features.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum RenderError {
+    /// The image can't be written
+    Io(io::Error),
+    /// The OpenEXR image can't be written
+    #[cfg(feature = "exr")]
+    Exr(exr::error::Error),
+    /// The camera is rendered before calling `build()`
+    NotBuilt,
+    /// The output buffer doesn't have one pixel per image pixel
+    BufferSize { expected: usize, actual: usize },
+}
+
+impl Display for RenderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "Failed to write the image: {err}"),
+            #[cfg(feature = "exr")]
+            Self::Exr(err) => write!(f, "Failed to write the OpenEXR image: {err}"),
+            Self::NotBuilt => write!(f, "The camera must be built before rendering"),
+            Self::BufferSize { expected, actual } => write!(
+                f,
+                "Buffer of {actual} pixels doesn't match the image of {expected} pixels"
+            ),
+        }
+    }
+}
+
+impl Error for RenderError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            #[cfg(feature = "exr")]
+            Self::Exr(err) => Some(err),
+            Self::NotBuilt | Self::BufferSize { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for RenderError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+#[cfg(feature = "exr")]
+impl From<exr::error::Error> for RenderError {
+    fn from(err: exr::error::Error) -> Self {
+        Self::Exr(err)
+    }
+}
@@ -0,0 +1,76 @@
+use crate::prelude::*;
+
+/// How the camera reduces the samples of a pixel to its color.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum PixelEstimator {
+    /// Arithmetic mean of the samples
+    #[default]
+    Mean,
+    /// Median of the samples, per color channel
+    ///
+    /// Robust to outlier samples (e.g. fireflies through glass), but biased.
+    Median,
+    /// Mean of the samples without the given fraction of the lowest and of the highest
+    /// ones, per color channel
+    ///
+    /// The fraction is clamped to [0, 0.5), `TrimmedMean(0.)` is the mean.
+    TrimmedMean(f64),
+}
+
+impl PixelEstimator {
+    // Reduce the samples of a pixel to its color, black if there is none.
+    pub(super) fn estimate(&self, samples: &[Color]) -> Color {
+        if samples.is_empty() {
+            return Color::zero();
+        }
+
+        let channel = |c: u8| {
+            let mut values = samples.iter().map(|sample| sample[c]).collect::<Vec<_>>();
+            values.sort_by(f64::total_cmp);
+
+            self.estimate_sorted(&values)
+        };
+
+        Color::new(channel(0), channel(1), channel(2))
+    }
+
+    // Reduce the sorted values of a channel, `values` is not empty.
+    fn estimate_sorted(&self, values: &[f64]) -> f64 {
+        let n = values.len();
+        let mean = |values: &[f64]| values.iter().sum::<f64>() / values.len() as f64;
+
+        match *self {
+            Self::Mean => mean(values),
+            Self::Median if n % 2 == 1 => values[n / 2],
+            Self::Median => mean(&values[n / 2 - 1..=n / 2]),
+            Self::TrimmedMean(fraction) => {
+                let trim = (fraction.clamp(0., 0.5) * n as f64) as usize;
+                // Keep at least one value
+                let trim = trim.min((n - 1) / 2);
+
+                mean(&values[trim..n - trim])
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimate_should_reject_outliers() {
+        let samples = [0.1, 0.2, 0.3, 0.2, 100.].map(Color::with_isotropic);
+
+        let mean = PixelEstimator::Mean.estimate(&samples);
+        assert!(common::relative_eq(mean.x, 20.16, None));
+        assert_eq!(PixelEstimator::Median.estimate(&samples).y, 0.2);
+        let trimmed = PixelEstimator::TrimmedMean(0.2).estimate(&samples);
+        assert!(common::relative_eq(trimmed.z, 0.7 / 3., None));
+
+        // Even count and degenerate cases
+        assert_eq!(PixelEstimator::Median.estimate(&samples[..4]).x, 0.2);
+        assert_eq!(PixelEstimator::TrimmedMean(0.9).estimate(&samples).x, 0.2);
+        assert_eq!(PixelEstimator::Median.estimate(&[]), Color::zero());
+    }
+}
@@ -0,0 +1,117 @@
+use std::f64::consts::TAU;
+
+use crate::Vec3;
+
+/// How the camera picks the sub-pixel and lens positions of the samples of a pixel.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Sampler {
+    /// Independent uniform random samples
+    #[default]
+    Random,
+    /// Halton low-discrepancy sequence, indexed by the sample number
+    ///
+    /// Each pixel shifts the sequence by its own offset (Cranley-Patterson rotation), so
+    /// adjacent pixels don't share the same pattern. The offset is a hash of the pixel
+    /// position, so the progressive passes of a time budget continue the same sequence.
+    Halton,
+}
+
+/// Bases of the Halton sequence, for the sub-pixel offset then the lens position.
+const HALTON_BASES: [u32; 4] = [2, 3, 5, 7];
+
+impl Sampler {
+    // Returns the offset decorrelating the sequence of the pixel at location i, j from
+    // its neighbours, always the same for a given pixel.
+    pub(super) fn pixel_rotation(&self, i: u32, j: u32) -> [f64; 4] {
+        match self {
+            Self::Random => [0.; 4],
+            Self::Halton => {
+                let pixel = mix64(((i as u64) << 32) | j as u64);
+                std::array::from_fn(|d| to_unit_interval(mix64(pixel.wrapping_add(d as u64))))
+            }
+        }
+    }
+}
+
+// Scramble the bits of `x` (the finalizer of MurmurHash3), as a deterministic hash.
+fn mix64(mut x: u64) -> u64 {
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    x ^ (x >> 33)
+}
+
+// Map the high 53 bits of `x` to [0, 1).
+fn to_unit_interval(x: u64) -> f64 {
+    (x >> 11) as f64 / (1u64 << 53) as f64
+}
+
+// Radical inverse of `index` in `base`, the `index`-th point of the Halton sequence.
+fn radical_inverse(mut index: u32, base: u32) -> f64 {
+    let inv_base = 1. / base as f64;
+    let mut scale = inv_base;
+    let mut result = 0.;
+
+    while index > 0 {
+        result += (index % base) as f64 * scale;
+        index /= base;
+        scale *= inv_base;
+    }
+
+    result
+}
+
+// Returns the 4D Halton point of the sample `index` shifted by `rotation`, in [0, 1)^4.
+pub(super) fn halton_sample(index: u32, rotation: &[f64; 4]) -> [f64; 4] {
+    // Skip the first point, which is 0 in every dimension.
+    std::array::from_fn(|d| (radical_inverse(index + 1, HALTON_BASES[d]) + rotation[d]).fract())
+}
+
+// Map a point of the unit square onto the unit disk, preserving uniformity.
+pub(super) fn square_to_unit_disk(u: f64, v: f64) -> Vec3 {
+    let r = u.sqrt();
+    let theta = TAU * v;
+
+    Vec3::with_xy(r * theta.cos(), r * theta.sin())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common;
+
+    #[test]
+    fn radical_inverse_should_work() {
+        assert_eq!(radical_inverse(1, 2), 0.5);
+        assert_eq!(radical_inverse(2, 2), 0.25);
+        assert_eq!(radical_inverse(3, 2), 0.75);
+        assert!(common::relative_eq(radical_inverse(5, 3), 7. / 9., None));
+    }
+
+    #[test]
+    fn halton_sample_should_stay_in_unit_square() {
+        let rotation = Sampler::Halton.pixel_rotation(3, 5);
+
+        for index in 0..100 {
+            let sample = halton_sample(index, &rotation);
+
+            assert!(sample.iter().all(|x| (0. ..1.).contains(x)));
+            assert!(square_to_unit_disk(sample[2], sample[3]).length() <= 1.);
+        }
+    }
+
+    #[test]
+    fn pixel_rotation_should_be_stable_across_passes() {
+        // Progressive rendering asks for the rotation of a pixel once per pass.
+        let first_pass = Sampler::Halton.pixel_rotation(3, 5);
+        let second_pass = Sampler::Halton.pixel_rotation(3, 5);
+        assert_eq!(first_pass, second_pass);
+        assert!(first_pass.iter().all(|x| (0. ..1.).contains(x)));
+
+        // Neighbours still get different offsets.
+        assert_ne!(first_pass, Sampler::Halton.pixel_rotation(4, 5));
+        assert_ne!(first_pass, Sampler::Halton.pixel_rotation(5, 3));
+        assert_eq!(Sampler::Random.pixel_rotation(3, 5), [0.; 4]);
+    }
+}
@@ -0,0 +1,160 @@
+use std::{
+    fmt::Display,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+/// Statistics of a finished render.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct RenderStats {
+    /// Number of pixels rendered, once per pass for a progressive render
+    pub pixels: u64,
+    /// Number of rays shot from the camera
+    pub primary_rays: u64,
+    /// Number of rays traced into the scene, including the scattered ones
+    pub total_rays: u64,
+    /// Number of shadow and occlusion rays, included in `total_rays`
+    pub shadow_rays: u64,
+    /// Wall-clock time of the render
+    pub elapsed: Duration,
+}
+
+impl RenderStats {
+    /// Average number of bounces per primary ray.
+    pub fn average_bounces(&self) -> f64 {
+        if self.primary_rays == 0 {
+            return 0.;
+        }
+
+        // No ray is traced with a zero max depth, so the total can be below the primary rays.
+        let bounces = self.total_rays - self.shadow_rays;
+        bounces.saturating_sub(self.primary_rays) as f64 / self.primary_rays as f64
+    }
+
+    /// Average number of rays traced per second.
+    pub fn rays_per_second(&self) -> f64 {
+        per_second(self.total_rays, self.elapsed)
+    }
+
+    /// Average number of pixels rendered per second.
+    pub fn pixels_per_second(&self) -> f64 {
+        per_second(self.pixels, self.elapsed)
+    }
+}
+
+// Rate of `count` over `elapsed`, 0 if no time elapsed.
+fn per_second(count: u64, elapsed: Duration) -> f64 {
+    if elapsed.is_zero() {
+        return 0.;
+    }
+
+    count as f64 / elapsed.as_secs_f64()
+}
+
+impl Display for RenderStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Rays: {} ({} primary, {} shadow), average bounces: {:.2}, elapsed time: {}ms, \
+            throughput: {:.2} Mrays/s, {:.0} pixels/s",
+            self.total_rays,
+            self.primary_rays,
+            self.shadow_rays,
+            self.average_bounces(),
+            self.elapsed.as_millis(),
+            self.rays_per_second() / 1e6,
+            self.pixels_per_second()
+        )
+    }
+}
+
+/// Counters shared by the render threads.
+#[derive(Debug)]
+pub(crate) struct RenderCounters {
+    pixels: AtomicU64,
+    primary_rays: AtomicU64,
+    total_rays: AtomicU64,
+    shadow_rays: AtomicU64,
+    started: Instant,
+}
+
+impl Default for RenderCounters {
+    fn default() -> Self {
+        Self {
+            pixels: AtomicU64::new(0),
+            primary_rays: AtomicU64::new(0),
+            total_rays: AtomicU64::new(0),
+            shadow_rays: AtomicU64::new(0),
+            started: Instant::now(),
+        }
+    }
+}
+
+impl RenderCounters {
+    /// Record the rays traced for one pixel, `total_rays` includes the `shadow_rays`.
+    pub(crate) fn add(&self, primary_rays: u64, total_rays: u64, shadow_rays: u64) {
+        self.pixels.fetch_add(1, Ordering::Relaxed);
+        self.primary_rays.fetch_add(primary_rays, Ordering::Relaxed);
+        self.total_rays.fetch_add(total_rays, Ordering::Relaxed);
+        self.shadow_rays.fetch_add(shadow_rays, Ordering::Relaxed);
+    }
+
+    /// Number of rays traced per second so far, since the counters were created.
+    pub(crate) fn rays_per_second(&self) -> f64 {
+        per_second(
+            self.total_rays.load(Ordering::Relaxed),
+            self.started.elapsed(),
+        )
+    }
+
+    /// Finish the counting, the render took `elapsed`.
+    pub(crate) fn into_stats(self, elapsed: Duration) -> RenderStats {
+        RenderStats {
+            pixels: self.pixels.into_inner(),
+            primary_rays: self.primary_rays.into_inner(),
+            total_rays: self.total_rays.into_inner(),
+            shadow_rays: self.shadow_rays.into_inner(),
+            elapsed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_stats_average_bounces_should_work() {
+        let counters = RenderCounters::default();
+        assert_eq!(counters.into_stats(Duration::ZERO).average_bounces(), 0.);
+
+        let counters = RenderCounters::default();
+        counters.add(10, 25, 0);
+        counters.add(10, 10, 0);
+        let stats = counters.into_stats(Duration::from_millis(5));
+
+        assert_eq!(stats.primary_rays, 20);
+        assert_eq!(stats.total_rays, 35);
+        assert_eq!(stats.average_bounces(), 0.75);
+
+        // Shadow rays are not bounces
+        let counters = RenderCounters::default();
+        counters.add(10, 40, 15);
+        assert_eq!(counters.into_stats(Duration::ZERO).average_bounces(), 1.5);
+    }
+
+    #[test]
+    fn render_stats_throughput_should_work() {
+        let stats = RenderStats {
+            pixels: 100,
+            primary_rays: 1000,
+            total_rays: 3000,
+            shadow_rays: 500,
+            elapsed: Duration::from_millis(500),
+        };
+
+        assert_eq!(stats.rays_per_second(), 6000.);
+        assert_eq!(stats.pixels_per_second(), 200.);
+        assert_eq!(RenderStats::default().rays_per_second(), 0.);
+    }
+}
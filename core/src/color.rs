@@ -1,4 +1,4 @@
-use std::sync::LazyLock;
+use std::{fmt::Display, sync::LazyLock};
 
 use crate::{Color, interval::Interval};
 
@@ -16,6 +16,204 @@ pub const BLACK: Color = Color {
     z: 0.,
 };
 
+impl Color {
+    /// 黑色
+    pub const BLACK: Color = BLACK;
+    /// 白色
+    pub const WHITE: Color = WHITE;
+    /// 灰色
+    pub const GRAY: Color = Color {
+        x: 0.5,
+        y: 0.5,
+        z: 0.5,
+    };
+    /// 红色
+    pub const RED: Color = Color {
+        x: 1.,
+        y: 0.,
+        z: 0.,
+    };
+    /// 绿色
+    pub const GREEN: Color = Color {
+        x: 0.,
+        y: 1.,
+        z: 0.,
+    };
+    /// 蓝色
+    pub const BLUE: Color = Color {
+        x: 0.,
+        y: 0.,
+        z: 1.,
+    };
+    /// 黄色
+    pub const YELLOW: Color = Color {
+        x: 1.,
+        y: 1.,
+        z: 0.,
+    };
+    /// 青色
+    pub const CYAN: Color = Color {
+        x: 0.,
+        y: 1.,
+        z: 1.,
+    };
+    /// 品红
+    pub const MAGENTA: Color = Color {
+        x: 1.,
+        y: 0.,
+        z: 1.,
+    };
+
+    /// Apply a linear to gamma transform to each component.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ray_tracing_core::Color;
+    /// let color = Color::new(0.25, 1., -1.).gamma_correct(2.);
+    /// assert_eq!(color, Color::new(0.5, 1., 0.));
+    /// ```
+    pub fn gamma_correct(&self, gamma: f64) -> Color {
+        Color::new(
+            linear_to_gamma_with(self.x, gamma),
+            linear_to_gamma_with(self.y, gamma),
+            linear_to_gamma_with(self.z, gamma),
+        )
+    }
+
+    /// Returns the relative luminance of a linear color (Rec. 709 weights).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ray_tracing_core::{common, Color};
+    /// assert!(common::relative_eq(Color::WHITE.luminance(), 1., None));
+    /// assert!(Color::GREEN.luminance() > Color::RED.luminance());
+    /// ```
+    pub fn luminance(&self) -> f64 {
+        0.2126 * self.x + 0.7152 * self.y + 0.0722 * self.z
+    }
+}
+
+/// Default gamma of the output
+pub const DEFAULT_GAMMA: f64 = 2.;
+
+/// Transfer function applied to the linear color before quantizing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorSpace {
+    /// The piecewise sRGB curve (with a linear toe near black)
+    Srgb,
+    /// A simple power curve with the given gamma
+    Gamma(f64),
+}
+
+// Default color space is gamma 2
+impl Default for ColorSpace {
+    fn default() -> Self {
+        Self::Gamma(DEFAULT_GAMMA)
+    }
+}
+
+/// A quantized 8-bit color, as written to the output image.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    /// Create a new 8-bit color.
+    pub fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Convert a linear color with the given gamma, like `translate_color_gamma`.
+    ///
+    /// # Note
+    ///
+    /// Components are clamped to [0, 0.999] after the gamma transform, scaled by 255 and
+    /// truncated, like the rendered images. So 1 maps to 254, and 255 is never produced.
+    /// Use `from_linear_clamped` to round to the nearest byte value instead.
+    pub fn from_linear(color: Color, gamma: f64) -> Self {
+        translate_color_gamma(color, gamma).into()
+    }
+
+    /// Convert a linear color with the given gamma, clamping the components to [0, 1]
+    /// and rounding to the nearest byte value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ray_tracing_core::{Color, color::Rgb};
+    /// let rgb = Rgb::from_linear_clamped(Color::new(1.5, 0.5, -1.), 1.);
+    /// assert_eq!(rgb, Rgb::new(255, 128, 0));
+    /// ```
+    pub fn from_linear_clamped(color: Color, gamma: f64) -> Self {
+        let quantize = |c: f64| (255. * linear_to_gamma_with(c.clamp(0., 1.), gamma)).round() as u8;
+
+        Self::new(quantize(color.x), quantize(color.y), quantize(color.z))
+    }
+
+    /// Convert back to a linear color with the given gamma, undoing `from_linear`
+    /// up to the quantization.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ray_tracing_core::{Color, color::Rgb};
+    /// let color = Rgb::new(255, 51, 0).to_linear(2.);
+    /// assert!(color.approx_eq(&Color::new(1., 0.04, 0.), None));
+    /// ```
+    pub fn to_linear(&self, gamma: f64) -> Color {
+        Color::from(*self).iter().map(|c| c.powf(gamma)).collect()
+    }
+}
+
+impl Display for Rgb {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.r, self.g, self.b)
+    }
+}
+
+// Converts a tuple of bytes, like (r, g, b), to Rgb.
+impl From<(u8, u8, u8)> for Rgb {
+    fn from((r, g, b): (u8, u8, u8)) -> Self {
+        Self::new(r, g, b)
+    }
+}
+
+// Converts Rgb to a tuple of bytes, like (r, g, b).
+impl From<Rgb> for (u8, u8, u8) {
+    fn from(rgb: Rgb) -> Self {
+        (rgb.r, rgb.g, rgb.b)
+    }
+}
+
+/// Converts a linear color like `translate_color`: gamma 2, then each component clamped
+/// to [0, 0.999] and truncated.
+impl From<Color> for Rgb {
+    fn from(color: Color) -> Self {
+        translate_color(color).into()
+    }
+}
+
+/// Converts the bytes to [0, 1] by dividing them by 255, as they are: no transfer
+/// function is undone, use `Rgb::to_linear` for the linear color.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ray_tracing_core::{Color, color::Rgb};
+/// assert_eq!(Color::from(Rgb::new(255, 51, 0)), Color::new(1., 0.2, 0.));
+/// ```
+impl From<Rgb> for Color {
+    fn from(rgb: Rgb) -> Self {
+        Self::new(rgb.r as f64, rgb.g as f64, rgb.b as f64) / 255.
+    }
+}
+
 static INTENSITY: LazyLock<Interval> = LazyLock::new(|| Interval::new(0., 0.999));
 
 /// Translate a color into a tuple of bytes
@@ -31,6 +229,25 @@ pub fn translate_color(pixel_color: Color) -> (u8, u8, u8) {
     )
 }
 
+/// Translate a color into a tuple of bytes with the given gamma.
+///
+/// # Note
+///
+/// gamma of 1 produces linear output.
+pub fn translate_color_gamma(pixel_color: Color, gamma: f64) -> (u8, u8, u8) {
+    if gamma == DEFAULT_GAMMA {
+        return translate_color(pixel_color);
+    }
+
+    let (r, g, b) = pixel_color.into();
+
+    (
+        (255. * INTENSITY.clamp(linear_to_gamma_with(r, gamma))) as u8,
+        (255. * INTENSITY.clamp(linear_to_gamma_with(g, gamma))) as u8,
+        (255. * INTENSITY.clamp(linear_to_gamma_with(b, gamma))) as u8,
+    )
+}
+
 /// Convert a linear component to a gamma component
 pub fn linear_to_gamma(linear_component: f64) -> f64 {
     if linear_component > 0. {
@@ -39,3 +256,75 @@ pub fn linear_to_gamma(linear_component: f64) -> f64 {
         0.
     }
 }
+
+/// Translate a color into a tuple of bytes in the given color space.
+pub fn translate_color_in(pixel_color: Color, color_space: ColorSpace) -> (u8, u8, u8) {
+    match color_space {
+        ColorSpace::Gamma(gamma) => translate_color_gamma(pixel_color, gamma),
+        ColorSpace::Srgb => {
+            let (r, g, b) = pixel_color.into();
+
+            (
+                (255. * INTENSITY.clamp(linear_to_srgb(r))) as u8,
+                (255. * INTENSITY.clamp(linear_to_srgb(g))) as u8,
+                (255. * INTENSITY.clamp(linear_to_srgb(b))) as u8,
+            )
+        }
+    }
+}
+
+/// Convert a linear component to a sRGB component (the sRGB OETF)
+pub fn linear_to_srgb(linear_component: f64) -> f64 {
+    if linear_component <= 0. {
+        0.
+    } else if linear_component <= 0.0031308 {
+        12.92 * linear_component
+    } else {
+        1.055 * linear_component.powf(1. / 2.4) - 0.055
+    }
+}
+
+/// Convert a linear component to a gamma component with the given gamma
+pub fn linear_to_gamma_with(linear_component: f64, gamma: f64) -> f64 {
+    if linear_component > 0. {
+        linear_component.powf(1. / gamma)
+    } else {
+        0.
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common;
+
+    #[test]
+    fn translate_color_gamma_should_work() {
+        let color = Color::new(0.25, 0.5, 0.);
+
+        assert_eq!(translate_color_gamma(color, 1.), (63, 127, 0));
+        assert_eq!(translate_color_gamma(color, 2.), translate_color(color));
+        assert_eq!(translate_color_gamma(color, 2.2), (135, 186, 0));
+    }
+
+    #[test]
+    fn rgb_from_linear_should_work() {
+        let color = Color::new(1., 0.25, 0.);
+
+        assert_eq!(Rgb::from_linear(color, 2.), Rgb::new(254, 127, 0));
+        assert_eq!(Rgb::from_linear_clamped(color, 2.), Rgb::new(255, 128, 0));
+        assert_eq!(Rgb::from_linear_clamped(-color, 2.), Rgb::new(0, 0, 0));
+    }
+
+    #[test]
+    fn linear_to_srgb_should_work() {
+        assert_eq!(linear_to_srgb(-1.), 0.);
+        assert!(common::relative_eq(linear_to_srgb(0.001), 0.01292, None));
+        assert!(common::relative_eq(linear_to_srgb(1.), 1., None));
+        assert!(common::relative_eq(
+            linear_to_srgb(0.5),
+            0.7353569830524495,
+            None
+        ));
+    }
+}
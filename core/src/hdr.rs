@@ -0,0 +1,140 @@
+use crate::prelude::*;
+
+/// An image of linear colors, before any transfer function or quantization.
+///
+/// Each pixel holds the mean of the samples accumulated into it, along with their count.
+/// Pixels are stored in scanline order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HdrBuffer {
+    width: u32,
+    height: u32,
+    pixels: Vec<Color>,
+    sample_counts: Vec<u32>,
+}
+
+impl HdrBuffer {
+    /// Create a black buffer of `width` x `height` pixels, without any sample.
+    pub fn new(width: u32, height: u32) -> Self {
+        let len = (width * height) as usize;
+
+        Self {
+            width,
+            height,
+            pixels: vec![Color::zero(); len],
+            sample_counts: vec![0; len],
+        }
+    }
+
+    // Create a buffer from the mean colors of its pixels and their numbers of samples.
+    pub(crate) fn from_pixels(
+        width: u32,
+        height: u32,
+        pixels: Vec<Color>,
+        sample_counts: Vec<u32>,
+    ) -> Self {
+        debug_assert_eq!(pixels.len(), (width * height) as usize);
+        debug_assert_eq!(sample_counts.len(), pixels.len());
+
+        Self {
+            width,
+            height,
+            pixels,
+            sample_counts,
+        }
+    }
+
+    /// Width of the buffer, in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Height of the buffer, in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Add a sample `color` to the pixel at location i, j.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the location is outside the buffer.
+    pub fn accumulate(&mut self, i: u32, j: u32, color: Color) {
+        let index = self.index(i, j);
+        let count = &mut self.sample_counts[index];
+        *count += 1;
+
+        // Running mean
+        let pixel = &mut self.pixels[index];
+        *pixel += (color - *pixel) / *count;
+    }
+
+    /// Number of samples accumulated into the pixel at location i, j.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the location is outside the buffer.
+    pub fn sample_count(&self, i: u32, j: u32) -> u32 {
+        self.sample_counts[self.index(i, j)]
+    }
+
+    /// Linear color of the pixel at location i, j, black without any sample.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the location is outside the buffer.
+    pub fn pixel(&self, i: u32, j: u32) -> Color {
+        self.pixels[self.index(i, j)]
+    }
+
+    /// Linear colors of all pixels, in scanline order.
+    pub fn pixels(&self) -> &[Color] {
+        &self.pixels
+    }
+
+    /// Convert all pixels to 8-bit colors with the given gamma, like `Rgb::from_linear`.
+    pub fn to_rgb(&self, gamma: f64) -> Vec<Rgb> {
+        self.to_rgb_in(color::ColorSpace::Gamma(gamma))
+    }
+
+    /// Convert all pixels to 8-bit colors in the given color space.
+    pub fn to_rgb_in(&self, color_space: color::ColorSpace) -> Vec<Rgb> {
+        self.pixels
+            .iter()
+            .map(|&pixel| color::translate_color_in(pixel, color_space).into())
+            .collect()
+    }
+
+    fn index(&self, i: u32, j: u32) -> usize {
+        assert!(
+            i < self.width && j < self.height,
+            "Pixel ({i}, {j}) is outside the {}x{} buffer",
+            self.width,
+            self.height
+        );
+
+        (j * self.width + i) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hdr_buffer_accumulate_should_average_samples() {
+        let mut buffer = HdrBuffer::new(2, 1);
+        buffer.accumulate(1, 0, Color::new(1., 2., 4.));
+        buffer.accumulate(1, 0, Color::new(3., 0., 4.));
+
+        assert_eq!(buffer.sample_count(0, 0), 0);
+        assert_eq!(buffer.sample_count(1, 0), 2);
+        assert_eq!(buffer.pixel(0, 0), Color::zero());
+        assert_eq!(buffer.pixel(1, 0), Color::new(2., 1., 4.));
+
+        // Values above 1 are kept until quantized
+        assert_eq!(
+            buffer.to_rgb(1.),
+            [Rgb::new(0, 0, 0), Rgb::new(254, 254, 254)]
+        );
+    }
+}
@@ -1,8 +1,16 @@
+mod clipped;
+mod csg;
 mod hittable_list;
+mod instance;
 mod sphere;
+mod triangle;
 
+pub use clipped::{ClipPlane, Clipped};
+pub use csg::{Csg, CsgOperation};
 pub use hittable_list::HittableList;
+pub use instance::{Instance, Transform};
 pub use sphere::Sphere;
+pub use triangle::Triangle;
 
 use std::sync::Arc;
 
@@ -13,8 +21,15 @@ pub struct HitRecord {
     pub t: f64,
     pub p: Point3,
     pub normal: Vec3,
+    /// True if the ray hits the outer side of the surface, i.e. enters a closed object.
     pub front_face: bool,
+    /// Unit tangent along the direction of increasing `u`, zero if undefined.
+    pub tangent: Vec3,
+    /// Surface coordinates `(u, v)` of the hit point, both in [0, 1].
+    pub uv: (f64, f64),
     pub material: Option<Arc<dyn Material>>,
+    /// Id of the hit object, assigned by the `HittableList` containing it.
+    pub object_id: Option<u32>,
 }
 
 impl HitRecord {
@@ -35,12 +50,30 @@ impl HitRecord {
         self
     }
 
+    /// Sets the parameter `tangent` of the hit record.
+    pub fn set_tangent(mut self, tangent: Vec3) -> Self {
+        self.tangent = tangent;
+        self
+    }
+
+    /// Sets the surface coordinates `(u, v)` of the hit record.
+    pub fn set_uv(mut self, u: f64, v: f64) -> Self {
+        self.uv = (u, v);
+        self
+    }
+
     /// Sets the parameter `material` of the hit record.
     pub fn set_material(mut self, material: Option<Arc<dyn Material>>) -> Self {
         self.material = material;
         self
     }
 
+    /// Sets the parameter `object_id` of the hit record.
+    pub fn set_object_id(mut self, object_id: Option<u32>) -> Self {
+        self.object_id = object_id;
+        self
+    }
+
     /// Sets the face normal based on the given ray and outward normal.
     ///
     /// # Note
@@ -60,4 +93,34 @@ impl HitRecord {
 /// Trait for objects that can be hit by rays.
 pub trait Hittable: Sync + Send {
     fn hit(&self, ray: &Ray, ray_t: Interval) -> Option<HitRecord>;
+
+    /// Returns true if the ray hits anything in `ray_t`, without building a hit record.
+    ///
+    /// Meant for visibility queries, e.g. shadow rays. Override it when an intersection
+    /// can be detected more cheaply than with `hit()`.
+    fn hit_any(&self, ray: &Ray, ray_t: Interval) -> bool {
+        self.hit(ray, ray_t).is_some()
+    }
+
+    /// Returns a uniformly random point on the surface with its outward unit normal,
+    /// e.g. to place lights or scatter objects on a surface.
+    ///
+    /// `None` if the object can't be sampled, which is the default.
+    fn random_surface_point(&self) -> Option<(Point3, Vec3)> {
+        None
+    }
+
+    /// Returns the area of the surface.
+    ///
+    /// `None` if the object can't be measured, which is the default.
+    fn surface_area(&self) -> Option<f64> {
+        None
+    }
+}
+
+/// Trait for closed objects with a well-defined inside, which can be combined by `Csg`.
+pub trait Solid: Hittable {
+    /// Returns the spans of the whole ray line (any `t`, not only positive) inside the
+    /// object, in order and disjoint.
+    fn hit_intervals(&self, ray: &Ray) -> Vec<Interval>;
 }
@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use crate::prelude::*;
+
+/// A plane cutting away everything on its negative side.
+#[derive(Debug, Clone, Copy)]
+pub struct ClipPlane {
+    pub point: Point3,
+    /// Points towards the kept side, not necessarily of unit length
+    pub normal: Vec3,
+}
+
+impl ClipPlane {
+    /// Create a new clip plane through `point`, keeping the side `normal` points to.
+    pub fn new(point: Point3, normal: Vec3) -> Self {
+        Self { point, normal }
+    }
+
+    /// Returns true if the point `p` is cut away by the plane.
+    pub fn clips(&self, p: &Point3) -> bool {
+        (*p - self.point).dot(&self.normal) < 0.
+    }
+}
+
+/// A geometry cut by clip planes, for cutaway renders.
+///
+/// The hits on the clipped parts are skipped and the ray continues to the next surface
+/// of the geometry, so the inside of closed objects becomes visible.
+pub struct Clipped {
+    geometry: Arc<dyn Hittable>,
+    planes: Vec<ClipPlane>,
+}
+
+impl Clipped {
+    /// Create `geometry` cut by `plane`.
+    pub fn new(geometry: Arc<dyn Hittable>, plane: ClipPlane) -> Self {
+        Self {
+            geometry,
+            planes: vec![plane],
+        }
+    }
+
+    /// Cut the geometry by one more plane.
+    pub fn add_plane(mut self, plane: ClipPlane) -> Self {
+        self.planes.push(plane);
+        self
+    }
+}
+
+impl Hittable for Clipped {
+    fn hit(&self, ray: &Ray, ray_t: Interval) -> Option<HitRecord> {
+        let mut t_min = ray_t.min;
+
+        loop {
+            let hit = self.geometry.hit(ray, Interval::new(t_min, ray_t.max))?;
+
+            if !self.planes.iter().any(|plane| plane.clips(&hit.p)) {
+                return Some(hit);
+            }
+
+            // Look for the next surface behind the clipped hit.
+            t_min = hit.t;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clipped_hit_should_skip_the_cut_away_part() {
+        let sphere: Arc<dyn Hittable> = Arc::new(Sphere::new(Point3::zero(), 1., None));
+        // Keep the lower half
+        let clipped = Clipped::new(sphere, ClipPlane::new(Point3::zero(), Vec3::with_z(-1.)));
+
+        // Through the cut, onto the inner side of the kept half
+        let ray = Ray::new(Point3::with_z(5.), Vec3::with_z(-1.));
+        let hit = clipped
+            .hit(&ray, Interval::new(0.001, f64::INFINITY))
+            .unwrap();
+        assert!(common::relative_eq(hit.t, 6., None));
+        assert!(!hit.front_face);
+
+        // Only through the cut away half
+        let ray = Ray::new(Point3::new(-5., 0., 0.5), Vec3::with_x(1.));
+        assert!(
+            clipped
+                .hit(&ray, Interval::new(0.001, f64::INFINITY))
+                .is_none()
+        );
+
+        // Cut by a second plane, keeping x > 0.5 only
+        let clipped = clipped.add_plane(ClipPlane::new(Point3::with_x(0.5), Vec3::with_x(1.)));
+        let ray = Ray::new(Point3::with_z(5.), Vec3::with_z(-1.));
+        assert!(
+            clipped
+                .hit(&ray, Interval::new(0.001, f64::INFINITY))
+                .is_none()
+        );
+        let ray = Ray::new(Point3::new(0.8, 0., 5.), Vec3::with_z(-1.));
+        assert!(
+            clipped
+                .hit(&ray, Interval::new(0.001, f64::INFINITY))
+                .is_some()
+        );
+    }
+}
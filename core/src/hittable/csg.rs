@@ -0,0 +1,166 @@
+use std::sync::Arc;
+
+use crate::prelude::*;
+
+/// Boolean operation combining the two operands of a `Csg`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CsgOperation {
+    /// Inside either operand
+    Union,
+    /// Inside both operands
+    Intersection,
+    /// Inside the first operand but not the second one
+    Difference,
+}
+
+impl CsgOperation {
+    fn contains(&self, inside_a: bool, inside_b: bool) -> bool {
+        match self {
+            Self::Union => inside_a || inside_b,
+            Self::Intersection => inside_a && inside_b,
+            Self::Difference => inside_a && !inside_b,
+        }
+    }
+}
+
+/// Which operand a boundary of a `Csg` belongs to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Side {
+    A,
+    B,
+}
+
+/// Constructive solid geometry, the boolean combination of two solids.
+///
+/// A `Csg` is a solid itself, so combinations can be nested.
+pub struct Csg {
+    a: Arc<dyn Solid>,
+    b: Arc<dyn Solid>,
+    operation: CsgOperation,
+}
+
+impl Csg {
+    /// Combine the solids `a` and `b` with `operation`.
+    pub fn new(a: Arc<dyn Solid>, b: Arc<dyn Solid>, operation: CsgOperation) -> Self {
+        Self { a, b, operation }
+    }
+
+    // Returns the boundaries of the combined solid along the ray line, in order and
+    // alternately entering and exiting, with the operand each one belongs to.
+    fn boundaries(&self, ray: &Ray) -> Vec<(f64, Side)> {
+        let mut events = self
+            .a
+            .hit_intervals(ray)
+            .into_iter()
+            .map(|span| (span, Side::A))
+            .chain(
+                self.b
+                    .hit_intervals(ray)
+                    .into_iter()
+                    .map(|span| (span, Side::B)),
+            )
+            .flat_map(|(span, side)| [(span.min, side, true), (span.max, side, false)])
+            .collect::<Vec<_>>();
+        events.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+        let (mut inside_a, mut inside_b) = (false, false);
+        let mut inside = false;
+        let mut boundaries = Vec::new();
+
+        // Apply the events at the same `t` together, so coincident surfaces don't leave
+        // empty spans.
+        for group in events.chunk_by(|x, y| x.0 == y.0) {
+            for &(_, side, entering) in group {
+                match side {
+                    Side::A => inside_a = entering,
+                    Side::B => inside_b = entering,
+                }
+            }
+
+            if self.operation.contains(inside_a, inside_b) != inside {
+                inside = !inside;
+                boundaries.push((group[0].0, group[0].1));
+            }
+        }
+
+        boundaries
+    }
+}
+
+impl Solid for Csg {
+    fn hit_intervals(&self, ray: &Ray) -> Vec<Interval> {
+        self.boundaries(ray)
+            .chunks_exact(2)
+            .map(|span| Interval::new(span[0].0, span[1].0))
+            .collect()
+    }
+}
+
+impl Hittable for Csg {
+    fn hit(&self, ray: &Ray, ray_t: Interval) -> Option<HitRecord> {
+        let (t, side) = self
+            .boundaries(ray)
+            .into_iter()
+            .find(|&(t, _)| ray_t.surrounds(t))?;
+
+        // Get the surface data from the operand owning the boundary.
+        let operand = match side {
+            Side::A => &self.a,
+            Side::B => &self.b,
+        };
+        let epsilon = 1e-9 * t.abs().max(1.);
+        let mut hit = operand.hit(ray, Interval::new(t - epsilon, t + epsilon))?;
+
+        // The surface of the subtracted solid faces inwards.
+        if side == Side::B && self.operation == CsgOperation::Difference {
+            hit.front_face = !hit.front_face;
+        }
+
+        Some(hit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spheres() -> (Arc<dyn Solid>, Arc<dyn Solid>) {
+        (
+            Arc::new(Sphere::new(Point3::zero(), 1., None)),
+            Arc::new(Sphere::new(Point3::with_z(1.), 1., None)),
+        )
+    }
+
+    #[test]
+    fn csg_hit_intervals_should_combine_operands() {
+        let ray = Ray::new(Point3::with_z(5.), Vec3::with_z(-1.));
+        // Along the ray: a is inside [4, 6], b is inside [3, 5]
+        let span = |operation| {
+            let (a, b) = spheres();
+            Csg::new(a, b, operation).hit_intervals(&ray)
+        };
+
+        assert_eq!(span(CsgOperation::Union), [Interval::new(3., 6.)]);
+        assert_eq!(span(CsgOperation::Intersection), [Interval::new(4., 5.)]);
+        assert_eq!(span(CsgOperation::Difference), [Interval::new(5., 6.)]);
+    }
+
+    #[test]
+    fn csg_difference_hit_should_face_out_of_the_hole() {
+        let (a, b) = spheres();
+        let csg = Csg::new(a, b, CsgOperation::Difference);
+
+        let ray = Ray::new(Point3::with_z(5.), Vec3::with_z(-1.));
+        let hit = csg.hit(&ray, Interval::new(0.001, f64::INFINITY)).unwrap();
+
+        // Entering through the inner side of the subtracted sphere
+        assert!(common::relative_eq(hit.t, 5., None));
+        assert!(hit.p.length() < 1e-9);
+        assert!(hit.front_face);
+        assert_eq!(hit.normal, Vec3::with_z(1.));
+
+        // Through the middle plane, where the subtracted sphere covers the other one
+        let ray = Ray::new(Point3::new(-5., 0., 0.5), Vec3::with_x(1.));
+        assert!(csg.hit(&ray, Interval::new(0.001, f64::INFINITY)).is_none());
+    }
+}
@@ -6,6 +6,9 @@ use std::{
 use crate::prelude::*;
 
 /// A collection of Hittable objects.
+///
+/// Each object is identified by its index in the list, which stays stable as objects are
+/// added. The index is reported as `HitRecord::object_id` when the object is hit.
 #[derive(Default)]
 pub struct HittableList {
     pub objects: Vec<Arc<dyn Hittable>>,
@@ -38,9 +41,11 @@ impl HittableList {
         Self { objects }
     }
 
-    /// Adds a `Hittable` object to the list.
-    pub fn add(&mut self, hittable: Arc<dyn Hittable>) {
+    /// Adds a `Hittable` object to the list, returns the id assigned to it.
+    pub fn add(&mut self, hittable: Arc<dyn Hittable>) -> u32 {
         self.objects.push(hittable);
+
+        (self.objects.len() - 1) as u32
     }
 }
 
@@ -49,15 +54,58 @@ impl Hittable for HittableList {
         let mut closest_so_far = ray_t.max;
         let mut hit_record = None;
 
-        for hittable in &self.objects {
+        for (id, hittable) in self.objects.iter().enumerate() {
             if let Some(hit) = hittable.hit(ray, Interval::new(ray_t.min, closest_so_far)) {
                 closest_so_far = hit.t;
-                hit_record = Some(hit);
+                hit_record = Some(hit.set_object_id(Some(id as u32)));
             }
         }
 
         hit_record
     }
+
+    fn hit_any(&self, ray: &Ray, ray_t: Interval) -> bool {
+        self.objects
+            .iter()
+            .any(|hittable| hittable.hit_any(ray, Interval::new(ray_t.min, ray_t.max)))
+    }
+
+    // Pick an object with a probability proportional to its area, so the points are
+    // uniform over the whole list. `None` if any object can't be measured.
+    fn random_surface_point(&self) -> Option<(Point3, Vec3)> {
+        let total_area = self.surface_area()?;
+        let mut target = common::random() * total_area;
+
+        for hittable in &self.objects {
+            let area = hittable.surface_area()?;
+            if target < area {
+                return hittable.random_surface_point();
+            }
+            target -= area;
+        }
+
+        // Rounding left the target past the last object.
+        self.objects.last()?.random_surface_point()
+    }
+
+    fn surface_area(&self) -> Option<f64> {
+        self.objects
+            .iter()
+            .map(|hittable| hittable.surface_area())
+            .sum()
+    }
+}
+
+impl Extend<Arc<dyn Hittable>> for HittableList {
+    fn extend<I: IntoIterator<Item = Arc<dyn Hittable>>>(&mut self, iter: I) {
+        self.objects.extend(iter);
+    }
+}
+
+impl FromIterator<Arc<dyn Hittable>> for HittableList {
+    fn from_iter<I: IntoIterator<Item = Arc<dyn Hittable>>>(iter: I) -> Self {
+        Self::from_hittables(iter.into_iter().collect())
+    }
 }
 
 impl Deref for HittableList {
@@ -92,4 +140,93 @@ mod tests {
         assert!(list.is_empty());
         assert_eq!(list.len(), 0);
     }
+
+    #[test]
+    fn hittable_list_hit_should_set_object_id() {
+        let mut list = HittableList::new();
+        let first = list.add(Arc::new(Sphere::new(Point3::with_z(-1.), 0.5, None)));
+        let second = list.add(Arc::new(Sphere::new(Point3::with_z(-3.), 0.5, None)));
+        assert_eq!((first, second), (0, 1));
+
+        let ray = Ray::new(Point3::zero(), Vec3::with_z(-1.));
+        let hit = list.hit(&ray, Interval::new(0.001, f64::INFINITY)).unwrap();
+        assert_eq!(hit.object_id, Some(first));
+
+        let ray = Ray::new(Point3::with_z(-2.), Vec3::with_z(-1.));
+        let hit = list.hit(&ray, Interval::new(0.001, f64::INFINITY)).unwrap();
+        assert_eq!(hit.object_id, Some(second));
+    }
+
+    #[test]
+    fn hittable_list_extend_and_collect_should_work() {
+        let sphere =
+            |x: f64| Arc::new(Sphere::new(Point3::with_x(x), 0.5, None)) as Arc<dyn Hittable>;
+
+        let mut list = (0..3).map(|i| sphere(i as f64)).collect::<HittableList>();
+        assert_eq!(list.len(), 3);
+
+        list.extend([sphere(3.), sphere(4.)]);
+        assert_eq!(list.len(), 5);
+
+        let ray = Ray::new(Point3::new(4., 0., 5.), Vec3::with_z(-1.));
+        let hit = list.hit(&ray, Interval::new(0.001, f64::INFINITY)).unwrap();
+        assert_eq!(hit.object_id, Some(4));
+    }
+
+    #[test]
+    fn hittable_list_hit_any_should_match_hit() {
+        let mut list = HittableList::new();
+        list.add(Arc::new(Sphere::new(Point3::with_z(-2.), 0.5, None)));
+        list.add(Arc::new(Triangle::new(
+            Point3::new(-1., -1., -4.),
+            Point3::new(1., -1., -4.),
+            Point3::new(0., 1., -4.),
+            None,
+        )));
+
+        for (direction, t_max) in [
+            (Vec3::with_z(-1.), f64::INFINITY),
+            (Vec3::with_z(-1.), 1.),
+            (Vec3::new(0.2, 0., -1.), f64::INFINITY),
+            (Vec3::with_y(1.), f64::INFINITY),
+        ] {
+            let ray = Ray::new(Point3::zero(), direction);
+            let interval = || Interval::new(0.001, t_max);
+
+            assert_eq!(
+                list.hit_any(&ray, interval()),
+                list.hit(&ray, interval()).is_some()
+            );
+        }
+    }
+
+    #[test]
+    fn hittable_list_random_surface_point_should_be_on_an_object() {
+        let mut list = HittableList::new();
+        assert!(list.random_surface_point().is_none());
+
+        list.add(Arc::new(Sphere::new(Point3::zero(), 1., None)));
+        list.add(Arc::new(Sphere::new(Point3::with_x(5.), 2., None)));
+        let area = list.surface_area().unwrap();
+        assert!(common::relative_eq(area, 20. * std::f64::consts::PI, None));
+
+        for _ in 0..100 {
+            let (p, normal) = list.random_surface_point().unwrap();
+            let (center, radius) = if p.x < 2. {
+                (Point3::zero(), 1.)
+            } else {
+                (Point3::with_x(5.), 2.)
+            };
+
+            assert!(common::relative_eq((p - center).length(), radius, None));
+            assert!((p - (center + radius * normal)).length() < 1e-12);
+        }
+
+        // A clipped object can't be measured
+        list.add(Arc::new(Clipped::new(
+            Arc::new(Sphere::new(Point3::zero(), 1., None)),
+            ClipPlane::new(Point3::zero(), Vec3::with_z(1.)),
+        )));
+        assert!(list.surface_area().is_none());
+    }
 }
@@ -0,0 +1,188 @@
+use std::sync::Arc;
+
+use crate::prelude::*;
+
+/// Placement of an instance: scaled uniformly, then rotated around the Y axis, then
+/// translated.
+#[derive(Debug, Clone, Copy)]
+pub struct Transform {
+    translation: Vec3,
+    sin_theta: f64,
+    cos_theta: f64,
+    scale: f64,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self {
+            translation: Vec3::zero(),
+            sin_theta: 0.,
+            cos_theta: 1.,
+            scale: 1.,
+        }
+    }
+}
+
+impl Transform {
+    /// Builds the identity transform.
+    pub fn builder() -> Self {
+        Self::default()
+    }
+
+    /// Sets the translation of the transform.
+    pub fn set_translation(mut self, translation: Vec3) -> Self {
+        self.translation = translation;
+        self
+    }
+
+    /// Sets the rotation around the Y axis of the transform, in degrees.
+    pub fn set_rotation_y(mut self, angle: f64) -> Self {
+        let radians = angle.to_radians();
+        self.sin_theta = radians.sin();
+        self.cos_theta = radians.cos();
+        self
+    }
+
+    /// Sets the uniform scale factor of the transform.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale` is not positive.
+    pub fn set_scale(mut self, scale: f64) -> Self {
+        assert!(scale > 0., "Scale must be positive, but got {scale}");
+        self.scale = scale;
+        self
+    }
+
+    // Move a ray from world space to object space, the parameter `t` along it is unchanged.
+    fn object_ray(&self, ray: &Ray) -> Ray {
+        Ray::new(
+            self.rotate_inverse(ray.origin - self.translation) / self.scale,
+            self.rotate_inverse(ray.direction) / self.scale,
+        )
+    }
+
+    // Rotate a direction from object space to world space.
+    fn rotate(&self, v: Vec3) -> Vec3 {
+        Vec3::new(
+            self.cos_theta * v.x + self.sin_theta * v.z,
+            v.y,
+            -self.sin_theta * v.x + self.cos_theta * v.z,
+        )
+    }
+
+    // Rotate a direction from world space to object space.
+    fn rotate_inverse(&self, v: Vec3) -> Vec3 {
+        Vec3::new(
+            self.cos_theta * v.x - self.sin_theta * v.z,
+            v.y,
+            self.sin_theta * v.x + self.cos_theta * v.z,
+        )
+    }
+}
+
+/// A shared geometry placed in the world by its own transform.
+///
+/// Many instances can reference the same geometry, which is stored only once.
+pub struct Instance {
+    geometry: Arc<dyn Hittable>,
+    transform: Transform,
+}
+
+impl Instance {
+    /// Create a new instance of `geometry` placed by `transform`.
+    pub fn new(geometry: Arc<dyn Hittable>, transform: Transform) -> Self {
+        Self {
+            geometry,
+            transform,
+        }
+    }
+}
+
+impl Hittable for Instance {
+    fn hit(&self, ray: &Ray, ray_t: Interval) -> Option<HitRecord> {
+        let transform = &self.transform;
+        let mut hit = self.geometry.hit(&transform.object_ray(ray), ray_t)?;
+
+        // Move the hit back into world space.
+        let outward_normal = if hit.front_face {
+            hit.normal
+        } else {
+            -hit.normal
+        };
+        hit.p = transform.translation + transform.scale * transform.rotate(hit.p);
+        hit.tangent = transform.rotate(hit.tangent);
+
+        Some(hit.set_face_normal(ray, transform.rotate(outward_normal)))
+    }
+
+    fn hit_any(&self, ray: &Ray, ray_t: Interval) -> bool {
+        self.geometry
+            .hit_any(&self.transform.object_ray(ray), ray_t)
+    }
+
+    fn random_surface_point(&self) -> Option<(Point3, Vec3)> {
+        let transform = &self.transform;
+        let (p, normal) = self.geometry.random_surface_point()?;
+
+        Some((
+            transform.translation + transform.scale * transform.rotate(p),
+            transform.rotate(normal),
+        ))
+    }
+
+    fn surface_area(&self) -> Option<f64> {
+        let scale = self.transform.scale;
+
+        Some(scale * scale * self.geometry.surface_area()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn instance_hit_should_apply_the_transform() {
+        let sphere: Arc<dyn Hittable> = Arc::new(Sphere::new(Point3::zero(), 1., None));
+        let instance = Instance::new(
+            sphere,
+            Transform::builder()
+                .set_translation(Vec3::with_z(-5.))
+                .set_scale(2.),
+        );
+
+        let ray = Ray::new(Point3::zero(), Vec3::with_z(-1.));
+        let hit = instance
+            .hit(&ray, Interval::new(0.001, f64::INFINITY))
+            .unwrap();
+
+        assert!(common::relative_eq(hit.t, 3., None));
+        assert!(hit.p.approx_eq(&Point3::with_z(-3.), None));
+        assert!(hit.normal.approx_eq(&Vec3::with_z(1.), None));
+        assert!(hit.front_face);
+    }
+
+    #[test]
+    fn instance_hit_should_rotate_around_y() {
+        // Facing +z in object space, facing +x once rotated.
+        let triangle: Arc<dyn Hittable> = Arc::new(Triangle::new(
+            Point3::zero(),
+            Point3::with_x(1.),
+            Point3::with_y(1.),
+            None,
+        ));
+        let instance = Instance::new(triangle, Transform::builder().set_rotation_y(90.));
+
+        let ray = Ray::new(Point3::new(5., 0.25, -0.25), Vec3::with_x(-1.));
+        let hit = instance
+            .hit(&ray, Interval::new(0.001, f64::INFINITY))
+            .unwrap();
+
+        assert!(common::relative_eq(hit.t, 5., None));
+        assert!((hit.p - Point3::new(0., 0.25, -0.25)).length() < 1e-12);
+        assert!((hit.normal - Vec3::with_x(1.)).length() < 1e-12);
+        assert!(hit.front_face);
+        assert!(instance.hit_any(&ray, Interval::new(0.001, f64::INFINITY)));
+    }
+}
@@ -16,10 +16,47 @@ impl Sphere {
             material,
         }
     }
-}
 
-impl Hittable for Sphere {
-    fn hit(&self, ray: &Ray, ray_t: Interval) -> Option<HitRecord> {
+    /// Returns the surface coordinates `(u, v)` of a point on the unit sphere.
+    ///
+    /// * `u`: angle around the Y axis from X=-1, mapped to [0, 1].
+    /// * `v`: angle from Y=-1 to Y=+1, mapped to [0, 1].
+    ///
+    /// # Note
+    ///
+    /// the parameter `p` is assumed to have unit length.
+    pub fn get_sphere_uv(p: &Point3) -> (f64, f64) {
+        let (theta, phi) = p.to_spherical();
+
+        (
+            phi / (2. * std::f64::consts::PI),
+            theta / std::f64::consts::PI,
+        )
+    }
+
+    /// Returns the unit tangent along increasing `u` at a point on the unit sphere,
+    /// that is around the Y axis, zero at the poles.
+    ///
+    /// # Note
+    ///
+    /// the parameter `p` is assumed to have unit length.
+    pub fn get_sphere_tangent(p: &Point3) -> Vec3 {
+        let tangent = Vec3::new(p.z, 0., -p.x);
+
+        if tangent.near_zero() {
+            Vec3::zero()
+        } else {
+            tangent.to_unit()
+        }
+    }
+
+    /// Returns true if the point `p` is strictly inside the sphere.
+    pub fn contains(&self, p: &Point3) -> bool {
+        (*p - self.center).length_squared() < self.radius * self.radius
+    }
+
+    // Returns the parameters `t` of both intersections of the ray line, in order.
+    fn roots(&self, ray: &Ray) -> Option<(f64, f64)> {
         let oc = self.center - ray.origin;
         let a = ray.direction.length_squared();
         let h = ray.direction.dot(&oc);
@@ -30,27 +67,126 @@ impl Hittable for Sphere {
             return None;
         }
 
-        let sqrtd = discriminant.sqrt();
+        // Avoid the cancellation of `h - sqrtd` when the roots have very different
+        // magnitudes, e.g. for a ray starting close to the surface.
+        let q = h + h.signum() * discriminant.sqrt();
+        if q == 0. {
+            return Some((0., 0.));
+        }
+        let (t0, t1) = (q / a, c / q);
 
-        // Find the nearest root that lies in the acceptable range.
-        let mut root = (h - sqrtd) / a;
-        if !ray_t.surrounds(root) {
-            root = (h + sqrtd) / a;
-            if !ray_t.surrounds(root) {
-                return None;
-            }
+        Some((t0.min(t1), t0.max(t1)))
+    }
+
+    // Returns the parameter `t` of the nearest intersection with the ray in `ray_t`.
+    fn nearest_root(&self, ray: &Ray, ray_t: Interval) -> Option<f64> {
+        let (near, far) = self.roots(ray)?;
+
+        // From inside, the near root is behind the origin and the ray can only exit.
+        if self.contains(&ray.origin) {
+            return Some(far).filter(|&root| ray_t.surrounds(root));
         }
 
-        let t = root;
+        // Find the nearest root that lies in the acceptable range.
+        [near, far].into_iter().find(|&root| ray_t.surrounds(root))
+    }
+}
+
+impl Solid for Sphere {
+    fn hit_intervals(&self, ray: &Ray) -> Vec<Interval> {
+        self.roots(ray)
+            .map(|(near, far)| Interval::new(near, far))
+            .into_iter()
+            .collect()
+    }
+}
+
+impl Hittable for Sphere {
+    // `front_face` is set if the ray enters the sphere, and unset if it exits it.
+    fn hit(&self, ray: &Ray, ray_t: Interval) -> Option<HitRecord> {
+        let t = self.nearest_root(ray, ray_t)?;
         let p = ray.at(t);
         let outward_normal = (p - self.center) / self.radius;
+        let (u, v) = Self::get_sphere_uv(&outward_normal);
+        let tangent = Self::get_sphere_tangent(&outward_normal);
 
         let hit_record = HitRecord::builder()
             .set_t(t)
             .set_p(p)
             .set_face_normal(ray, outward_normal)
+            .set_uv(u, v)
+            .set_tangent(tangent)
             .set_material(self.material.clone());
 
         Some(hit_record)
     }
+
+    fn hit_any(&self, ray: &Ray, ray_t: Interval) -> bool {
+        self.nearest_root(ray, ray_t).is_some()
+    }
+
+    fn random_surface_point(&self) -> Option<(Point3, Vec3)> {
+        let normal = Vec3::random_unit_vector();
+
+        Some((self.center + self.radius * normal, normal))
+    }
+
+    fn surface_area(&self) -> Option<f64> {
+        Some(4. * std::f64::consts::PI * self.radius * self.radius)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_sphere_uv_should_work() {
+        assert_eq!(Sphere::get_sphere_uv(&Point3::with_x(1.)), (0.5, 0.5));
+        assert_eq!(Sphere::get_sphere_uv(&Point3::with_y(1.)).1, 1.);
+        assert_eq!(Sphere::get_sphere_uv(&Point3::with_y(-1.)).1, 0.);
+        assert_eq!(Sphere::get_sphere_uv(&Point3::with_z(1.)), (0.25, 0.5));
+        assert_eq!(Sphere::get_sphere_uv(&Point3::with_z(-1.)), (0.75, 0.5));
+    }
+
+    #[test]
+    fn get_sphere_tangent_should_follow_u() {
+        let p = Point3::new(1., 2., -3.).to_unit();
+        let tangent = Sphere::get_sphere_tangent(&p);
+        let (u, _) = Sphere::get_sphere_uv(&p);
+        let (u_next, _) = Sphere::get_sphere_uv(&(p + 1e-6 * tangent).to_unit());
+
+        assert!(tangent.dot(&p).abs() < 1e-12);
+        assert!(u_next > u);
+        assert_eq!(
+            Sphere::get_sphere_tangent(&Point3::with_y(1.)),
+            Vec3::zero()
+        );
+    }
+
+    #[test]
+    fn sphere_hit_from_inside_should_exit() {
+        let sphere = Sphere::new(Point3::zero(), 1., None);
+        assert!(sphere.contains(&Point3::with_x(0.5)));
+        assert!(!sphere.contains(&Point3::with_x(1.)));
+
+        let ray = Ray::new(Point3::with_x(0.5), Vec3::with_x(-2.));
+        let hit = sphere
+            .hit(&ray, Interval::new(0.001, f64::INFINITY))
+            .unwrap();
+
+        assert!(common::relative_eq(hit.t, 0.75, None));
+        assert_eq!(hit.p, Point3::with_x(-1.));
+        assert_eq!(hit.normal, Vec3::with_x(1.));
+        assert!(!hit.front_face);
+
+        // Just inside the surface, as a refracted ray
+        let ray = Ray::new(Point3::with_x(1. - 1e-12), Vec3::with_x(-1.));
+        let hit = sphere
+            .hit(&ray, Interval::new(0.001, f64::INFINITY))
+            .unwrap();
+
+        assert!(common::relative_eq(hit.t, 2., None));
+        assert!(!hit.front_face);
+    }
 }
@@ -0,0 +1,147 @@
+use std::sync::Arc;
+
+use crate::prelude::*;
+
+/// Triangle given by its three vertices.
+///
+/// Intersected with the Möller–Trumbore algorithm, so no plane data is precomputed.
+pub struct Triangle {
+    a: Point3,
+    b: Point3,
+    c: Point3,
+    material: Option<Arc<dyn Material>>,
+}
+
+impl Triangle {
+    /// Create a new triangle, the outward normal follows the counter-clockwise winding
+    /// `a -> b -> c`.
+    pub fn new(a: Point3, b: Point3, c: Point3, material: Option<Arc<dyn Material>>) -> Self {
+        Self { a, b, c, material }
+    }
+}
+
+impl Hittable for Triangle {
+    fn hit(&self, ray: &Ray, ray_t: Interval) -> Option<HitRecord> {
+        let edge1 = self.b - self.a;
+        let edge2 = self.c - self.a;
+        let pvec = ray.direction.cross(&edge2);
+        let det = edge1.dot(&pvec);
+
+        // The ray is parallel to the triangle.
+        if det.abs() < 1e-8 {
+            return None;
+        }
+
+        let inv_det = 1. / det;
+        let tvec = ray.origin - self.a;
+
+        // Barycentric coordinates of the hit point, (1 - u - v) is the weight of `a`.
+        let u = tvec.dot(&pvec) * inv_det;
+        if !(0. ..=1.).contains(&u) {
+            return None;
+        }
+
+        let qvec = tvec.cross(&edge1);
+        let v = ray.direction.dot(&qvec) * inv_det;
+        if v < 0. || u + v > 1. {
+            return None;
+        }
+
+        let t = edge2.dot(&qvec) * inv_det;
+        if !ray_t.surrounds(t) {
+            return None;
+        }
+
+        let outward_normal = edge1.cross(&edge2).to_unit();
+
+        let hit_record = HitRecord::builder()
+            .set_t(t)
+            .set_p(ray.at(t))
+            .set_face_normal(ray, outward_normal)
+            .set_uv(u, v)
+            .set_tangent(edge1.to_unit())
+            .set_material(self.material.clone());
+
+        Some(hit_record)
+    }
+
+    fn random_surface_point(&self) -> Option<(Point3, Vec3)> {
+        let (mut u, mut v) = (common::random(), common::random());
+        // Fold the half of the parallelogram outside the triangle back into it.
+        if u + v > 1. {
+            (u, v) = (1. - u, 1. - v);
+        }
+
+        let (edge1, edge2) = (self.b - self.a, self.c - self.a);
+
+        Some((
+            self.a + u * edge1 + v * edge2,
+            edge1.cross(&edge2).to_unit(),
+        ))
+    }
+
+    fn surface_area(&self) -> Option<f64> {
+        Some(0.5 * (self.b - self.a).cross(&(self.c - self.a)).length())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triangle() -> Triangle {
+        Triangle::new(
+            Point3::new(0., 0., -1.),
+            Point3::new(1., 0., -1.),
+            Point3::new(0., 1., -1.),
+            None,
+        )
+    }
+
+    #[test]
+    fn triangle_hit_should_work() {
+        let ray = Ray::new(Point3::new(0.25, 0.5, 0.), Vec3::with_z(-1.));
+        let hit = triangle()
+            .hit(&ray, Interval::new(0.001, f64::INFINITY))
+            .unwrap();
+
+        assert!(common::relative_eq(hit.t, 1., None));
+        assert!(hit.p.approx_eq(&Point3::new(0.25, 0.5, -1.), None));
+        assert_eq!(hit.normal, Vec3::with_z(1.));
+        assert!(hit.front_face);
+        assert_eq!(hit.uv, (0.25, 0.5));
+        assert_eq!(hit.tangent, Vec3::with_x(1.));
+    }
+
+    #[test]
+    fn triangle_hit_should_miss_outside() {
+        let ray = Ray::new(Point3::new(0.75, 0.5, 0.), Vec3::with_z(-1.));
+        assert!(
+            triangle()
+                .hit(&ray, Interval::new(0.001, f64::INFINITY))
+                .is_none()
+        );
+
+        // parallel to the triangle
+        let ray = Ray::new(Point3::new(0.25, 0.25, -1.), Vec3::with_x(1.));
+        assert!(
+            triangle()
+                .hit(&ray, Interval::new(0.001, f64::INFINITY))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn triangle_random_surface_point_should_stay_inside() {
+        let triangle = triangle();
+        assert_eq!(triangle.surface_area(), Some(0.5));
+
+        for _ in 0..100 {
+            let (p, normal) = triangle.random_surface_point().unwrap();
+
+            assert_eq!(p.z, -1.);
+            assert!(p.x >= 0. && p.y >= 0. && p.x + p.y <= 1.);
+            assert_eq!(normal, Vec3::with_z(1.));
+        }
+    }
+}
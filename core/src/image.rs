@@ -0,0 +1,266 @@
+use std::{error::Error, fmt::Display, fs, io, path::Path};
+
+use crate::color::Rgb;
+
+/// Load a PPM image (P3 or P6), like the ones written by `Camera::render_to`.
+///
+/// Returns the width, the height and the pixels in scanline order, see `parse_pnm`.
+pub fn load_pnm<P: AsRef<Path>>(path: P) -> Result<(u32, u32, Vec<Rgb>), io::Error> {
+    parse_pnm(&fs::read(path)?)
+}
+
+/// Parse a PPM image, in plain (P3) or binary (P6) format.
+///
+/// Returns the width, the height and the pixels in scanline order. The components are
+/// rescaled from the maximum value of the header to [0, 255]. Comments (`#` to the end
+/// of the line) and any whitespace are allowed between the header values.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ray_tracing_core::{color::Rgb, image};
+/// let (width, height, pixels) = image::parse_pnm(b"P3\n# red\n1 1\n255\n255 0 0\n").unwrap();
+/// assert_eq!((width, height), (1, 1));
+/// assert_eq!(pixels, [Rgb::new(255, 0, 0)]);
+/// ```
+pub fn parse_pnm(data: &[u8]) -> Result<(u32, u32, Vec<Rgb>), io::Error> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+    let mut pos = 0;
+
+    let binary = match next_token(data, &mut pos) {
+        Some(b"P3") => false,
+        Some(b"P6") => true,
+        _ => return Err(invalid("expected the magic number P3 or P6")),
+    };
+    let mut number = |what: &str| {
+        next_token(data, &mut pos)
+            .and_then(|token| std::str::from_utf8(token).ok()?.parse::<u32>().ok())
+            .ok_or_else(|| invalid(&format!("invalid {what}")))
+    };
+    let width = number("width")?;
+    let height = number("height")?;
+    let max_value = number("maximum value")?;
+    if !(1..=u16::MAX as u32).contains(&max_value) {
+        return Err(invalid("maximum value must be in [1, 65535]"));
+    }
+
+    let len = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|pixels| pixels.checked_mul(3))
+        .ok_or_else(|| invalid("image too large"))?;
+    let components = if binary {
+        // A single whitespace separates the header from the pixels.
+        if !data.get(pos).is_some_and(u8::is_ascii_whitespace) {
+            return Err(invalid("missing whitespace after the header"));
+        }
+        pos += 1;
+
+        let size = if max_value < 256 { 1 } else { 2 };
+        let bytes = len
+            .checked_mul(size)
+            .and_then(|bytes| data.get(pos..pos + bytes))
+            .ok_or_else(|| invalid("truncated pixel data"))?;
+
+        bytes
+            .chunks_exact(size)
+            .map(|c| match *c {
+                [v] => v as u32,
+                [hi, lo] => u16::from_be_bytes([hi, lo]) as u32,
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>()
+    } else {
+        (0..len)
+            .map(|_| number("pixel value"))
+            .collect::<Result<Vec<_>, _>>()?
+    };
+
+    if components.iter().any(|&c| c > max_value) {
+        return Err(invalid("pixel value above the maximum value"));
+    }
+
+    // Rescale to [0, 255], rounding to the nearest byte value.
+    let scale = |c: u32| ((c * 255 + max_value / 2) / max_value) as u8;
+    let pixels = components
+        .chunks_exact(3)
+        .map(|c| Rgb::new(scale(c[0]), scale(c[1]), scale(c[2])))
+        .collect();
+
+    Ok((width, height, pixels))
+}
+
+/// Differences between two images of the same size, per color component.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageDiff {
+    /// Largest difference of a component
+    pub max_difference: u8,
+    /// Mean absolute difference of the components
+    pub mean_absolute_error: f64,
+    /// Peak signal-to-noise ratio in dB, infinite for identical images
+    pub psnr: f64,
+}
+
+/// Error raised when comparing images with different numbers of pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeMismatch {
+    pub left: usize,
+    pub right: usize,
+}
+
+impl Display for SizeMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Images of {} and {} pixels can't be compared",
+            self.left, self.right
+        )
+    }
+}
+
+impl Error for SizeMismatch {}
+
+/// Compare two images pixel by pixel, e.g. a render against a reference image, to check
+/// they are within a tolerance rather than bit-exact.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ray_tracing_core::{color::Rgb, image};
+/// let diff = image::compare(&[Rgb::new(10, 20, 30)], &[Rgb::new(13, 20, 30)]).unwrap();
+/// assert_eq!(diff.max_difference, 3);
+/// assert_eq!(diff.mean_absolute_error, 1.);
+/// assert!(image::compare(&[Rgb::default()], &[]).is_err());
+/// ```
+pub fn compare(left: &[Rgb], right: &[Rgb]) -> Result<ImageDiff, SizeMismatch> {
+    if left.len() != right.len() {
+        return Err(SizeMismatch {
+            left: left.len(),
+            right: right.len(),
+        });
+    }
+
+    let differences = left
+        .iter()
+        .zip(right)
+        .flat_map(|(l, r)| [l.r.abs_diff(r.r), l.g.abs_diff(r.g), l.b.abs_diff(r.b)])
+        .collect::<Vec<_>>();
+    // Empty images are identical.
+    let count = differences.len().max(1) as f64;
+    let sum = differences.iter().map(|&d| d as f64).sum::<f64>();
+    let squared_sum = differences.iter().map(|&d| (d as f64).powi(2)).sum::<f64>();
+
+    Ok(ImageDiff {
+        max_difference: differences.iter().copied().max().unwrap_or_default(),
+        mean_absolute_error: sum / count,
+        psnr: 10. * (255_f64.powi(2) / (squared_sum / count)).log10(),
+    })
+}
+
+// Return the next header token, skipping whitespace and comments, `None` at the end of data.
+fn next_token<'a>(data: &'a [u8], pos: &mut usize) -> Option<&'a [u8]> {
+    loop {
+        match data.get(*pos)? {
+            b'#' => {
+                while data.get(*pos).is_some_and(|&c| c != b'\n') {
+                    *pos += 1;
+                }
+            }
+            c if c.is_ascii_whitespace() => *pos += 1,
+            _ => break,
+        }
+    }
+
+    let start = *pos;
+    while data
+        .get(*pos)
+        .is_some_and(|&c| !c.is_ascii_whitespace() && c != b'#')
+    {
+        *pos += 1;
+    }
+
+    Some(&data[start..*pos])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn parse_pnm_should_work() {
+        // Comments and arbitrary whitespace in the header, 4-bit components
+        let (width, height, pixels) =
+            parse_pnm(b"P3 # plain\n2\t1 # size\n\n15\n15 0 0   0 5\n15").unwrap();
+        assert_eq!((width, height), (2, 1));
+        assert_eq!(pixels, [Rgb::new(255, 0, 0), Rgb::new(0, 85, 255)]);
+
+        // Binary, with one and two bytes per component
+        let mut data = b"P6\n# binary\n2 1\n255\n".to_vec();
+        data.extend([255, 0, 10, 1, 2, 3]);
+        let (_, _, pixels) = parse_pnm(&data).unwrap();
+        assert_eq!(pixels, [Rgb::new(255, 0, 10), Rgb::new(1, 2, 3)]);
+
+        let mut data = b"P6 1 1 65535 ".to_vec();
+        data.extend([0xff, 0xff, 0x80, 0x00, 0, 0]);
+        let (_, _, pixels) = parse_pnm(&data).unwrap();
+        assert_eq!(pixels, [Rgb::new(255, 128, 0)]);
+    }
+
+    #[test]
+    fn parse_pnm_should_reject_invalid_input() {
+        assert!(parse_pnm(b"P5\n1 1\n255\n0").is_err());
+        assert!(parse_pnm(b"P3\n1 x\n255\n0 0 0").is_err());
+        assert!(parse_pnm(b"P3\n1 1\n0\n0 0 0").is_err());
+        assert!(parse_pnm(b"P3\n1 1\n255\n0 0").is_err());
+        assert!(parse_pnm(b"P3\n1 1\n15\n0 0 16").is_err());
+        assert!(parse_pnm(b"P6\n2 1\n255\n\x01\x02\x03").is_err());
+    }
+
+    #[test]
+    fn compare_should_work() {
+        let image = [Rgb::new(0, 0, 0), Rgb::new(100, 200, 255)];
+
+        let same = compare(&image, &image).unwrap();
+        assert_eq!((same.max_difference, same.mean_absolute_error), (0, 0.));
+        assert_eq!(same.psnr, f64::INFINITY);
+
+        // One component off by 255: MSE of 255² / 6
+        let other = [Rgb::new(0, 0, 255), Rgb::new(100, 200, 255)];
+        let diff = compare(&image, &other).unwrap();
+        assert_eq!(diff.max_difference, 255);
+        assert_eq!(diff.mean_absolute_error, 42.5);
+        assert!(common::relative_eq(diff.psnr, 10. * 6_f64.log10(), None));
+
+        assert_eq!(
+            compare(&image, &image[..1]),
+            Err(SizeMismatch { left: 2, right: 1 })
+        );
+    }
+
+    #[test]
+    fn parse_pnm_should_read_back_the_rendered_image() {
+        let mut world = HittableList::new();
+        world.add(Arc::new(Sphere::new(Point3::zero(), 1., None)));
+        let world = Arc::new(world);
+        let camera = || {
+            Camera::builder()
+                .set_image_width(6)
+                .set_samples_per_pixel(1)
+                .set_look_from(Point3::with_z(3.))
+                .set_look_at(Point3::zero())
+                .set_render_mode(RenderMode::Normals)
+                .set_sampler(Sampler::Halton)
+                .set_quiet(true)
+                .build()
+        };
+
+        let mut ppm = Vec::new();
+        camera().render_to(world.clone(), &mut ppm).unwrap();
+        let mut expected = vec![Rgb::default(); 36];
+        camera().render_into(world.as_ref(), &mut expected).unwrap();
+
+        assert_eq!(parse_pnm(&ppm).unwrap(), (6, 6, expected));
+    }
+}
@@ -1,5 +1,6 @@
 /// Manage real-valued intervals with a minimum and a maximum.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Interval {
     pub min: f64,
     pub max: f64,
@@ -63,6 +64,20 @@ impl Interval {
     pub fn clamp(&self, x: f64) -> f64 {
         x.clamp(self.min, self.max)
     }
+
+    /// Check if the interval shares at least one value with another one, bounds included
+    /// (like `contains`), so intervals touching at a bound overlap.
+    ///
+    /// An empty interval (`min > max`) overlaps nothing.
+    ///
+    /// # Tip
+    ///
+    /// min <= other.max && other.min <= max, both intervals non-empty
+    pub fn overlaps(&self, other: &Self) -> bool {
+        let non_empty = self.min <= self.max && other.min <= other.max;
+
+        non_empty && self.min <= other.max && other.min <= self.max
+    }
 }
 
 // Default interval is empty
@@ -121,4 +136,26 @@ mod tests {
         assert_eq!(interval.clamp(2.5), 2.5);
         assert_eq!(interval.clamp(3.0), 2.5);
     }
+
+    #[test]
+    fn interval_overlaps_should_work() {
+        let interval = Interval::new(1., 2.);
+
+        assert!(interval.overlaps(&Interval::new(1.5, 3.)));
+        assert!(interval.overlaps(&Interval::new(1.2, 1.8)));
+        assert!(interval.overlaps(&UNIVERSE));
+        // Touching at a bound
+        assert!(interval.overlaps(&Interval::new(2., 3.)));
+        assert!(Interval::new(0., 1.).overlaps(&interval));
+        // Disjoint
+        assert!(!interval.overlaps(&Interval::new(2.1, 3.)));
+        assert!(!Interval::new(-1., 0.9).overlaps(&interval));
+        assert!(!interval.overlaps(&EMPTY));
+        // Empty intervals, either way
+        assert!(!EMPTY.overlaps(&interval));
+        assert!(!EMPTY.overlaps(&UNIVERSE));
+        assert!(!UNIVERSE.overlaps(&EMPTY));
+        assert!(!Interval::new(3., 1.).overlaps(&Interval::new(0., 5.)));
+        assert!(!Interval::new(0., 5.).overlaps(&Interval::new(3., 1.)));
+    }
 }
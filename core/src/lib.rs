@@ -1,10 +1,17 @@
+pub mod animation;
 pub mod camera;
 pub mod color;
 pub mod common;
+pub mod hdr;
 pub mod hittable;
+pub mod image;
 pub mod interval;
+pub mod light;
 pub mod material;
+pub mod onb;
 pub mod ray;
+#[cfg(feature = "scene")]
+pub mod scene;
 pub mod vec3;
 
 pub use vec3::Vec3 as Point3;
@@ -13,12 +20,23 @@ pub use vec3::{Vec3, Vec3Iter};
 
 /// Prelude module for importing commonly used types and traits.
 pub mod prelude {
-    pub use crate::camera::Camera;
-    pub use crate::color;
+    pub use crate::camera::{
+        Camera, CameraError, PixelEstimator, ProjectionMode, RenderError, RenderMode, RenderStats,
+        Sampler,
+    };
+    pub use crate::color::{self, Rgb};
     pub use crate::common::{self, Degrees, Radians};
-    pub use crate::hittable::{HitRecord, Hittable, HittableList, Sphere};
+    pub use crate::hdr::HdrBuffer;
+    pub use crate::hittable::{
+        ClipPlane, Clipped, Csg, CsgOperation, HitRecord, Hittable, HittableList, Instance, Solid,
+        Sphere, Transform, Triangle,
+    };
     pub use crate::interval::Interval;
-    pub use crate::material::{Dielectric, Lambertian, Material, Metal};
+    pub use crate::light::PointLight;
+    pub use crate::material::{
+        Conductor, Dielectric, DispersiveDielectric, Lambertian, Material, Metal, Mix,
+    };
+    pub use crate::onb::Onb;
     pub use crate::ray::Ray;
     pub use crate::vec3::Vec3 as Point3;
     pub use crate::vec3::Vec3 as Color;
@@ -0,0 +1,75 @@
+use crate::prelude::*;
+
+/// An infinitely small light source, sampled analytically for crisp shadows.
+#[derive(Debug, Clone, Copy)]
+pub struct PointLight {
+    /// Position of the light
+    pub position: Point3,
+    /// Color of the emitted light
+    pub color: Color,
+    /// Intensity of the light, the received light falls off with the squared distance
+    pub intensity: f64,
+}
+
+impl PointLight {
+    /// Create a new point light.
+    pub fn new(position: Point3, color: Color, intensity: f64) -> Self {
+        Self {
+            position,
+            color,
+            intensity,
+        }
+    }
+
+    /// Returns the light arriving at the hit point, weighted by the cosine to its
+    /// normal, or `None` if the light is behind the surface or occluded.
+    ///
+    /// # Note
+    ///
+    /// The occlusion test is a shadow ray towards the light using `world.hit_any`.
+    pub fn illuminate<H: Hittable>(&self, hit: &HitRecord, world: &H) -> Option<Color> {
+        let to_light = self.position - hit.p;
+        let distance_squared = to_light.length_squared();
+        let cosine = hit.normal.dot(&to_light) / distance_squared.sqrt();
+
+        if cosine <= 0. {
+            return None;
+        }
+
+        // The light is at `t = 1` along the unnormalized shadow ray.
+        let shadow_ray = Ray::new(hit.p, to_light);
+        if world.hit_any(&shadow_ray, Interval::new(0.001, 1.)) {
+            return None;
+        }
+
+        Some(self.intensity * cosine / distance_squared * self.color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    #[test]
+    fn illuminate_should_work() {
+        let light = PointLight::new(Point3::new(0., 2., 0.), Color::one(), 8.);
+        let down = Ray::new(Point3::with_y(1.), Vec3::with_y(-1.));
+        let hit = HitRecord::builder().set_face_normal(&down, Vec3::with_y(1.));
+        let mut world = HittableList::new();
+
+        // Lit from straight above, 2 units away.
+        let lit = light.illuminate(&hit, &world).unwrap();
+        assert!(lit.approx_eq(&Color::new(2., 2., 2.), None));
+
+        // Behind the surface.
+        let up = Ray::new(Point3::with_y(-1.), Vec3::with_y(1.));
+        let below = HitRecord::builder().set_face_normal(&up, Vec3::with_y(-1.));
+        assert!(light.illuminate(&below, &world).is_none());
+
+        // Occluded by a sphere between the point and the light.
+        world.add(Arc::new(Sphere::new(Point3::with_y(1.), 0.5, None)));
+        assert!(light.illuminate(&hit, &world).is_none());
+    }
+}
@@ -1,13 +1,42 @@
+mod conductor;
 mod dielectric;
+mod dispersive;
 mod lambertian;
 mod metal;
+mod mix;
+mod mtl;
 
+pub use conductor::Conductor;
 pub use dielectric::Dielectric;
+pub use dispersive::DispersiveDielectric;
 pub use lambertian::Lambertian;
 pub use metal::Metal;
+pub use mix::Mix;
+pub use mtl::{load_mtl, parse_mtl};
 
 use crate::{Color, hittable::HitRecord, ray::Ray};
 
 pub trait Material: Sync + Send {
     fn scatter(&self, ray_in: &Ray, hit: &HitRecord) -> Option<(Color, Ray)>;
+
+    /// Returns the probability density of the material scattering `ray_in` into the
+    /// direction of `scattered`, materials scattering into a single direction return 0.
+    fn scattering_pdf(&self, _ray_in: &Ray, _hit: &HitRecord, _scattered: &Ray) -> f64 {
+        0.
+    }
+
+    /// Whether the material scatters diffusely, point lights are only sampled at
+    /// diffuse hits.
+    fn is_diffuse(&self) -> bool {
+        false
+    }
+
+    /// Like `scatter()`, also returning whether the scattering was diffuse.
+    ///
+    /// Override it when the lobe is chosen per scattered ray, like `Mix` does, so
+    /// `is_diffuse()` can't tell.
+    fn scatter_lobe(&self, ray_in: &Ray, hit: &HitRecord) -> Option<(Color, Ray, bool)> {
+        self.scatter(ray_in, hit)
+            .map(|(attenuation, scattered)| (attenuation, scattered, self.is_diffuse()))
+    }
 }
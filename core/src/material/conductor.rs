@@ -0,0 +1,66 @@
+use crate::{prelude::*, vec3};
+
+/// Conductor material, a metal whose reflectance follows the Fresnel equations.
+#[derive(Default)]
+pub struct Conductor {
+    // Reflectance at normal incidence
+    f0: Color,
+    fuzz: f64,
+}
+
+impl Conductor {
+    /// Create a new conductor material with the given reflectance at normal incidence
+    /// and fuzziness.
+    pub fn new(albedo: Color, fuzz: f64) -> Self {
+        Self {
+            f0: albedo,
+            fuzz: fuzz.clamp(0., 1.),
+        }
+    }
+
+    /// Create a new conductor material from the complex index of refraction `eta + i*k`
+    /// given per color channel.
+    pub fn from_ior(eta: Color, k: Color, fuzz: f64) -> Self {
+        let k2 = k * k;
+        let f0 = ((eta - Color::one()) * (eta - Color::one()) + k2)
+            / ((eta + Color::one()) * (eta + Color::one()) + k2);
+
+        Self::new(f0, fuzz)
+    }
+
+    fn reflectance(&self, cosine: f64) -> Color {
+        // Use Schlick's approximation for reflectance.
+        self.f0 + (Color::one() - self.f0) * (1. - cosine).powi(5)
+    }
+}
+
+impl Material for Conductor {
+    fn scatter(&self, ray_in: &Ray, hit: &HitRecord) -> Option<(Color, Ray)> {
+        let unit_direction = ray_in.direction_unit();
+        let cos_theta = (-unit_direction).dot(&hit.normal).clamp(0., 1.);
+
+        let reflected = vec3::reflect(&unit_direction, &hit.normal);
+        let reflected = reflected + self.fuzz * Vec3::random_unit_vector();
+        let scattered = Ray::new(hit.p, reflected);
+
+        if scattered.direction.dot(&hit.normal) > 0. {
+            Some((self.reflectance(cos_theta), scattered))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conductor_reflectance_should_brighten_at_grazing_angle() {
+        let conductor = Conductor::new(Color::new(0.9, 0.6, 0.2), 0.);
+
+        assert_eq!(conductor.reflectance(1.), Color::new(0.9, 0.6, 0.2));
+        assert_eq!(conductor.reflectance(0.), Color::one());
+        assert!(conductor.reflectance(0.2).z > conductor.reflectance(0.8).z);
+    }
+}
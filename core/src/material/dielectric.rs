@@ -5,11 +5,22 @@ pub struct Dielectric {
     // Refractive index in vacuum or air, or the ratio of the material's refractive index over
     // the refractive index of the enclosing media
     refraction_index: f64,
+    // Attenuation of the scattered rays, white for clear glass
+    tint: Color,
 }
 
 impl Dielectric {
     pub fn new(refraction_index: f64) -> Self {
-        Self { refraction_index }
+        Self::new_colored(refraction_index, Color::one())
+    }
+
+    /// Create a colored dielectric (e.g. tinted glass) attenuating the scattered rays
+    /// by the given tint color.
+    pub fn new_colored(refraction_index: f64, tint: Color) -> Self {
+        Self {
+            refraction_index,
+            tint,
+        }
     }
 
     fn reflect(cosine: f64, refraction_index: f64) -> f64 {
@@ -28,7 +39,7 @@ impl Material for Dielectric {
         } else {
             self.refraction_index
         };
-        let unit_direction = ray_in.direction.to_unit();
+        let unit_direction = ray_in.direction_unit();
         let cos_theta = (-unit_direction).dot(&hit.normal).min(1.);
         let sin_theta = (1. - cos_theta * cos_theta).sqrt();
         let cannot_refract = ri * sin_theta > 1.;
@@ -41,6 +52,24 @@ impl Material for Dielectric {
             vec3::refract(&unit_direction, &hit.normal, ri)
         };
 
-        Some((Color::one(), Ray::new(hit.p, direction)))
+        Some((self.tint, Ray::new(hit.p, direction)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dielectric_scatter_should_attenuate_by_the_tint() {
+        let tint = Color::new(0.9, 0.5, 0.2);
+        let ray = Ray::new(Point3::new(-1., 1., 0.), Vec3::new(1., -1., 0.));
+        let hit = HitRecord::builder().set_face_normal(&ray, Vec3::with_y(1.));
+        let attenuation = |dielectric: &Dielectric| dielectric.scatter(&ray, &hit).unwrap().0;
+
+        // Whether the ray is reflected or refracted
+        let colored = Dielectric::new_colored(1.5, tint);
+        assert!((0..64).all(|_| attenuation(&colored) == tint));
+        assert_eq!(attenuation(&Dielectric::new(1.5)), Color::one());
     }
 }
@@ -0,0 +1,110 @@
+use crate::prelude::*;
+
+// Wavelengths (µm) of the Fraunhofer C, d and F lines, defining the Abbe number
+const LAMBDA_C: f64 = 0.6563;
+const LAMBDA_D: f64 = 0.5876;
+const LAMBDA_F: f64 = 0.4861;
+
+/// Wavelengths (µm) standing for the red, green and blue channels
+const CHANNEL_WAVELENGTHS: [f64; 3] = [0.65, 0.55, 0.45];
+
+/// Dielectric whose refractive index depends on the wavelength, splitting white light
+/// into colors (e.g. a prism).
+///
+/// The first dispersive hit of a path picks a single color channel at random, which the
+/// scattered ray keeps (`Ray::channel`) for the rest of the path. Every dispersive hit
+/// refracts it with the index of that channel's wavelength.
+pub struct DispersiveDielectric {
+    // Coefficients `(a, b)` of Cauchy's equation `n = a + b / λ²`
+    cauchy: (f64, f64),
+}
+
+impl DispersiveDielectric {
+    /// Create a dispersive dielectric with the refractive index `refraction_index` at
+    /// the d line (587.6 nm) and the Abbe number `abbe_number`.
+    ///
+    /// The lower the Abbe number, the stronger the dispersion, e.g. about 64 for crown
+    /// glass and 20 to 30 for dense flint glass.
+    pub fn new(refraction_index: f64, abbe_number: f64) -> Self {
+        let b = (refraction_index - 1.)
+            / (abbe_number * (1. / (LAMBDA_F * LAMBDA_F) - 1. / (LAMBDA_C * LAMBDA_C)));
+        let a = refraction_index - b / (LAMBDA_D * LAMBDA_D);
+
+        Self { cauchy: (a, b) }
+    }
+
+    /// Returns the refractive index at the wavelength `lambda` (µm).
+    pub fn refraction_index(&self, lambda: f64) -> f64 {
+        let (a, b) = self.cauchy;
+
+        a + b / (lambda * lambda)
+    }
+}
+
+impl Material for DispersiveDielectric {
+    fn scatter(&self, ray_in: &Ray, hit: &HitRecord) -> Option<(Color, Ray)> {
+        // Weight a newly picked channel by 3 since it's chosen a third of the time.
+        let (channel, weight) = match ray_in.channel {
+            Some(channel) => (channel, 1.),
+            None => (((common::random() * 3.) as u8).min(2), 3.),
+        };
+        let index = self.refraction_index(CHANNEL_WAVELENGTHS[channel as usize]);
+
+        let mut weights = [0.; 3];
+        weights[channel as usize] = weight;
+        let attenuation = Color::from(weights);
+
+        Dielectric::new(index)
+            .scatter(ray_in, hit)
+            .map(|(tint, scattered)| (attenuation * tint, scattered.set_channel(Some(channel))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refraction_index_should_follow_the_abbe_number() {
+        let glass = DispersiveDielectric::new(1.5, 40.);
+        let (n_c, n_d, n_f) = (
+            glass.refraction_index(LAMBDA_C),
+            glass.refraction_index(LAMBDA_D),
+            glass.refraction_index(LAMBDA_F),
+        );
+
+        assert!(common::relative_eq(n_d, 1.5, None));
+        assert!(common::relative_eq((n_d - 1.) / (n_f - n_c), 40., None));
+        // Blue light bends more than red light.
+        assert!(glass.refraction_index(0.45) > glass.refraction_index(0.65));
+    }
+
+    #[test]
+    fn scatter_should_keep_a_single_channel() {
+        let glass = DispersiveDielectric::new(1.5, 20.);
+        let ray = Ray::new(Point3::new(-1., 1., 0.), Vec3::new(1., -1., 0.));
+        let hit = HitRecord::builder().set_face_normal(&ray, Vec3::with_y(1.));
+
+        // A white ray picks a channel, weighted by 3.
+        let (attenuation, scattered) = glass.scatter(&ray, &hit).unwrap();
+        let channel = scattered.channel.unwrap();
+        assert_eq!(attenuation[channel], 3.);
+        assert_eq!(attenuation.iter().sum::<f64>(), 3.);
+
+        // A split ray keeps its channel, unweighted.
+        let blue = || Ray::new(ray.origin, ray.direction).set_channel(Some(2));
+        let (attenuation, scattered) = glass.scatter(&blue(), &hit).unwrap();
+        assert_eq!(attenuation, Color::with_z(1.));
+        assert_eq!(scattered.channel, Some(2));
+
+        // Blue light bends more than red light, towards the normal.
+        let refracted = |ray: &dyn Fn() -> Ray| {
+            (0..1000)
+                .map(|_| glass.scatter(&ray(), &hit).unwrap().1.direction_unit())
+                .find(|direction| direction.y < 0.)
+                .unwrap()
+        };
+        let red = || Ray::new(ray.origin, ray.direction).set_channel(Some(0));
+        assert!(refracted(&blue).x < refracted(&red).x);
+    }
+}
@@ -1,3 +1,5 @@
+use std::f64::consts::PI;
+
 use crate::prelude::*;
 
 /// Lambertian material
@@ -15,13 +17,20 @@ impl Lambertian {
 
 impl Material for Lambertian {
     fn scatter(&self, _ray_in: &Ray, hit: &HitRecord) -> Option<(Color, Ray)> {
-        let mut scatter_direction = hit.normal + Vec3::random_unit_vector();
-
-        // Catch degenerate scatter direction
-        if scatter_direction.near_zero() {
-            scatter_direction = hit.normal;
-        }
+        // Cosine-weighted direction in the hemisphere around the normal
+        let scatter_direction =
+            Onb::from_normal(&hit.normal).local(Vec3::random_cosine_direction());
 
         Some((self.albedo, Ray::new(hit.p, scatter_direction)))
     }
+
+    fn scattering_pdf(&self, _ray_in: &Ray, hit: &HitRecord, scattered: &Ray) -> f64 {
+        let cos_theta = hit.normal.dot(&scattered.direction_unit());
+
+        (cos_theta / PI).max(0.)
+    }
+
+    fn is_diffuse(&self) -> bool {
+        true
+    }
 }
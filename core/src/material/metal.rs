@@ -5,20 +5,39 @@ use crate::{prelude::*, vec3};
 pub struct Metal {
     albedo: Color,
     fuzz: f64,
+    // Scatters in place of the constant albedo when the Fresnel term is enabled
+    conductor: Option<Conductor>,
 }
 
 impl Metal {
     /// Create a new metal material with the given albedo color and fuzziness.
     pub fn new(albedo: Color, fuzz: f64) -> Self {
+        Self::with_fresnel(albedo, fuzz, false)
+    }
+
+    /// Like `new()`, but if `fresnel` is true the metal scatters like
+    /// `Conductor::new(albedo, fuzz)`: the albedo is taken as the reflectance at normal
+    /// incidence and Schlick's approximation varies it with the angle of the ray.
+    ///
+    /// # Note
+    ///
+    /// The Fresnel term does not darken the metal at grazing angles, the reflectance
+    /// tends to white there instead. Prefer `Conductor` directly for new code.
+    pub fn with_fresnel(albedo: Color, fuzz: f64, fresnel: bool) -> Self {
         Self {
             albedo,
             fuzz: fuzz.clamp(0., 1.),
+            conductor: fresnel.then(|| Conductor::new(albedo, fuzz)),
         }
     }
 }
 
 impl Material for Metal {
     fn scatter(&self, ray_in: &Ray, hit: &HitRecord) -> Option<(Color, Ray)> {
+        if let Some(conductor) = &self.conductor {
+            return conductor.scatter(ray_in, hit);
+        }
+
         let reflected = vec3::reflect(&ray_in.direction, &hit.normal);
         let reflected = reflected.to_unit() + self.fuzz * Vec3::random_unit_vector();
         let scattered = Ray::new(hit.p, reflected);
@@ -30,3 +49,37 @@ impl Material for Metal {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metal_fresnel_should_change_reflectance_with_angle() {
+        let albedo = Color::new(0.8, 0.6, 0.2);
+        let down = Ray::new(Point3::zero(), Vec3::with_y(-1.));
+        let hit = HitRecord::builder().set_face_normal(&down, Vec3::with_y(1.));
+        let reflectance = |metal: &Metal, direction: Vec3| {
+            metal
+                .scatter(&Ray::new(Point3::with_y(1.), direction), &hit)
+                .unwrap()
+                .0
+        };
+        let (head_on, grazing) = (Vec3::with_y(-1.), Vec3::new(1., -0.05, 0.));
+
+        let metal = Metal::with_fresnel(albedo, 0., true);
+        assert!(reflectance(&metal, head_on).approx_eq(&albedo, None));
+        assert!(reflectance(&metal, grazing).z > reflectance(&metal, head_on).z);
+        // Same as the conductor
+        let conductor = Conductor::new(albedo, 0.);
+        let ray = Ray::new(Point3::with_y(1.), grazing);
+        assert_eq!(
+            metal.scatter(&ray, &hit).unwrap().0,
+            conductor.scatter(&ray, &hit).unwrap().0
+        );
+
+        // Constant albedo without the flag
+        let metal = Metal::new(albedo, 0.);
+        assert_eq!(reflectance(&metal, grazing), albedo);
+    }
+}
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use crate::prelude::*;
+
+/// Blend of two materials, e.g. a clear coat over a diffuse base.
+///
+/// Each scattered ray is handled by one of the two materials chosen at random, so the
+/// Monte Carlo average converges to the blend.
+pub struct Mix {
+    a: Arc<dyn Material>,
+    b: Arc<dyn Material>,
+    // Probability of the material `b` handling a ray
+    factor: f64,
+}
+
+impl Mix {
+    /// Create a new mix material, `factor` is the weight of `b`. (0 is `a`, 1 is `b`)
+    pub fn new(a: Arc<dyn Material>, b: Arc<dyn Material>, factor: f64) -> Self {
+        Self {
+            a,
+            b,
+            factor: factor.clamp(0., 1.),
+        }
+    }
+}
+
+impl Material for Mix {
+    fn scatter(&self, ray_in: &Ray, hit: &HitRecord) -> Option<(Color, Ray)> {
+        self.scatter_lobe(ray_in, hit)
+            .map(|(attenuation, scattered, _)| (attenuation, scattered))
+    }
+
+    fn scatter_lobe(&self, ray_in: &Ray, hit: &HitRecord) -> Option<(Color, Ray, bool)> {
+        if common::random() < self.factor {
+            self.b.scatter_lobe(ray_in, hit)
+        } else {
+            self.a.scatter_lobe(ray_in, hit)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mix_should_reduce_to_one_material_at_the_ends() {
+        let (red, blue) = (Color::with_x(1.), Color::with_z(1.));
+        let mix = |factor| {
+            Mix::new(
+                Arc::new(Lambertian::new(red)),
+                Arc::new(Metal::new(blue, 0.)),
+                factor,
+            )
+        };
+        let ray = Ray::new(Point3::with_y(1.), Vec3::with_y(-1.));
+        let hit = HitRecord::builder().set_face_normal(&ray, Vec3::with_y(1.));
+        let always = |mix: Mix, expected: Color| {
+            (0..64).all(|_| mix.scatter(&ray, &hit).unwrap().0 == expected)
+        };
+
+        assert!(always(mix(0.), red));
+        assert!(always(mix(1.), blue));
+        // The factor is clamped
+        assert!(always(mix(-1.), red));
+        assert!(always(mix(2.), blue));
+    }
+}
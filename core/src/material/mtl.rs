@@ -0,0 +1,212 @@
+use std::{collections::HashMap, fs, io, path::Path, sync::Arc};
+
+use crate::prelude::*;
+
+/// Load the materials of a Wavefront MTL file, keyed by their `newmtl` name.
+///
+/// See `parse_mtl` for how the statements map to materials.
+pub fn load_mtl<P: AsRef<Path>>(path: P) -> Result<HashMap<String, Arc<dyn Material>>, io::Error> {
+    parse_mtl(&fs::read_to_string(path)?)
+}
+
+/// Parse the materials of a Wavefront MTL description, keyed by their `newmtl` name.
+///
+/// * `d < 1` or `Tr > 0` (transparent): `Dielectric` with the index `Ni` (1.5 by default),
+///   tinted by `Tf` if given.
+/// * `Kd` only: `Lambertian` with the albedo `Kd`.
+/// * `Ks` only: `Metal` with the albedo `Ks`, the fuzz is derived from the exponent `Ns`.
+/// * both `Kd` and `Ks`: `Mix` of the two above, weighted by their brightest component.
+///
+/// Other statements (like `illum`, or the texture maps `map_Kd` etc.) are ignored.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ray_tracing_core::material;
+/// let materials = material::parse_mtl(
+///     "newmtl red\nKd 0.8 0.1 0.1\n\nnewmtl glass\nd 0.1\nNi 1.5\n",
+/// )
+/// .unwrap();
+/// assert!(materials.contains_key("red"));
+/// assert!(materials.contains_key("glass"));
+/// ```
+pub fn parse_mtl(mtl: &str) -> Result<HashMap<String, Arc<dyn Material>>, io::Error> {
+    let mut materials = HashMap::new();
+    let mut current: Option<(String, MtlParams)> = None;
+
+    for (index, line) in mtl.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        let mut tokens = line.split_whitespace();
+        let Some(keyword) = tokens.next() else {
+            continue;
+        };
+        let values = tokens.collect::<Vec<_>>();
+        let invalid = |message: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {}: {message}: {line}", index + 1),
+            )
+        };
+
+        if keyword == "newmtl" {
+            if let Some((name, params)) = current.take() {
+                materials.insert(name, params.into_material());
+            }
+
+            let name = values.join(" ");
+            if name.is_empty() {
+                return Err(invalid("missing material name"));
+            }
+            current = Some((name, MtlParams::default()));
+            continue;
+        }
+
+        let Some((_, params)) = current.as_mut() else {
+            return Err(invalid("statement before any newmtl"));
+        };
+        let numbers = || {
+            values
+                .iter()
+                .map(|value| value.parse::<f64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| invalid("invalid number"))
+        };
+        let color = || match *numbers()?.as_slice() {
+            // A single value is a gray color.
+            [v] => Ok(Color::with_isotropic(v)),
+            [r, g, b] => Ok(Color::new(r, g, b)),
+            _ => Err(invalid("expected 1 or 3 color components")),
+        };
+        let scalar = || match *numbers()?.as_slice() {
+            [v] => Ok(v),
+            _ => Err(invalid("expected 1 value")),
+        };
+
+        match keyword {
+            "Kd" => params.diffuse = Some(color()?),
+            "Ks" => params.specular = Some(color()?),
+            "Tf" => params.transmission = Some(color()?),
+            "Ns" => params.shininess = scalar()?,
+            "Ni" => params.refraction_index = scalar()?,
+            "d" => params.dissolve = scalar()?,
+            "Tr" => params.dissolve = 1. - scalar()?,
+            _ => {}
+        }
+    }
+
+    if let Some((name, params)) = current {
+        materials.insert(name, params.into_material());
+    }
+
+    Ok(materials)
+}
+
+// The statements of one `newmtl` block.
+struct MtlParams {
+    diffuse: Option<Color>,
+    specular: Option<Color>,
+    transmission: Option<Color>,
+    shininess: f64,
+    refraction_index: f64,
+    dissolve: f64,
+}
+
+impl Default for MtlParams {
+    fn default() -> Self {
+        Self {
+            diffuse: None,
+            specular: None,
+            transmission: None,
+            shininess: 0.,
+            refraction_index: 1.5,
+            dissolve: 1.,
+        }
+    }
+}
+
+impl MtlParams {
+    fn into_material(self) -> Arc<dyn Material> {
+        if self.dissolve < 1. {
+            let tint = self.transmission.unwrap_or(Color::one());
+
+            return Arc::new(Dielectric::new_colored(self.refraction_index, tint));
+        }
+
+        let brightest = |color: Color| color.x.max(color.y).max(color.z);
+        let diffuse = self.diffuse.filter(|kd| brightest(*kd) > 0.);
+        let specular = self.specular.filter(|ks| brightest(*ks) > 0.);
+        // Ns goes from 0 (rough) to 1000 (mirror).
+        let fuzz = 1. - (self.shininess / 1000.).clamp(0., 1.);
+
+        match (diffuse, specular) {
+            (Some(kd), Some(ks)) => Arc::new(Mix::new(
+                Arc::new(Lambertian::new(kd)),
+                Arc::new(Metal::new(ks, fuzz)),
+                brightest(ks) / (brightest(kd) + brightest(ks)),
+            )),
+            (None, Some(ks)) => Arc::new(Metal::new(ks, fuzz)),
+            (kd, None) => Arc::new(Lambertian::new(kd.unwrap_or_default())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_mtl_should_work() {
+        let materials = parse_mtl(
+            "# exported materials
+            newmtl diffuse
+            Kd 0.8 0.1 0.1
+            illum 2
+            map_Kd red.png
+
+            newmtl mirror
+            Kd 0 0 0
+            Ks 0.9
+            Ns 1000
+
+            newmtl plastic
+            Kd 0.2 0.2 0.8
+            Ks 0.5 0.5 0.5
+
+            newmtl glass
+            Tr 0.9
+            Ni 1.45",
+        )
+        .unwrap();
+
+        let mut names = materials.keys().cloned().collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, ["diffuse", "glass", "mirror", "plastic"]);
+
+        let ray = Ray::new(Point3::new(-1., 1., 0.), Vec3::new(1., -1., 0.));
+        let hit = HitRecord::builder().set_face_normal(&ray, Vec3::with_y(1.));
+        let scatter = |name: &str| materials[name].scatter(&ray, &hit).unwrap();
+
+        assert!(materials["diffuse"].is_diffuse());
+
+        // Ns 1000 is a perfect mirror
+        let (attenuation, reflected) = scatter("mirror");
+        assert_eq!(attenuation, Color::with_isotropic(0.9));
+        assert_eq!(reflected.direction, scatter("mirror").1.direction);
+        assert!(reflected.direction.x > 0. && reflected.direction.y > 0.);
+        assert!(!materials["mirror"].is_diffuse());
+
+        // Most rays go through the glass, untinted
+        let (attenuation, _) = scatter("glass");
+        assert_eq!(attenuation, Color::one());
+        assert!((0..16).any(|_| scatter("glass").1.direction.y < 0.));
+        assert!(!materials["glass"].is_diffuse());
+    }
+
+    #[test]
+    fn parse_mtl_should_reject_invalid_input() {
+        assert!(parse_mtl("Kd 1 1 1").is_err());
+        assert!(parse_mtl("newmtl red\nKd 1 x 1").is_err());
+        assert!(parse_mtl("newmtl red\nKd 1 1").is_err());
+        assert!(parse_mtl("newmtl\n").is_err());
+    }
+}
@@ -0,0 +1,75 @@
+use crate::Vec3;
+
+/// Orthonormal basis, a local frame around a direction.
+///
+/// The `w` axis is the given normal, `u` and `v` span the tangent plane.
+#[derive(Debug, Clone, Copy)]
+pub struct Onb {
+    /// First tangent axis
+    pub u: Vec3,
+    /// Second tangent axis
+    pub v: Vec3,
+    /// Normal axis
+    pub w: Vec3,
+}
+
+impl Onb {
+    /// Build an orthonormal basis with the `w` axis along `n`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ray_tracing_core::{onb::Onb, Vec3};
+    /// let onb = Onb::from_normal(&Vec3::new(0., 0., 2.));
+    /// assert!(onb.local(Vec3::with_z(1.)).approx_eq(&Vec3::with_z(1.), None));
+    /// ```
+    ///
+    /// # Note
+    ///
+    /// `n` does not need to be of unit length, but it must not be zero.
+    pub fn from_normal(n: &Vec3) -> Self {
+        let w = n.to_unit();
+        // Pick a helper axis far from `w`, so the cross product never degenerates.
+        let a = if w.x.abs() > 0.9 {
+            Vec3::with_y(1.)
+        } else {
+            Vec3::with_x(1.)
+        };
+        let v = w.cross(&a).to_unit();
+        let u = w.cross(&v);
+
+        Self { u, v, w }
+    }
+
+    /// Transform the local coordinates `a` to world space.
+    pub fn local(&self, a: Vec3) -> Vec3 {
+        a.x * self.u + a.y * self.v + a.z * self.w
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common;
+
+    #[test]
+    fn from_normal_should_be_orthonormal() {
+        for n in [
+            Vec3::with_x(1.),
+            Vec3::with_y(-1.),
+            Vec3::with_z(1.),
+            Vec3::new(0.95, 0.1, 0.1),
+            Vec3::new(-1., 2., 3.),
+        ] {
+            let onb = Onb::from_normal(&n);
+
+            for axis in [onb.u, onb.v, onb.w] {
+                assert!(common::relative_eq(axis.length(), 1., None));
+            }
+            assert!(onb.u.dot(&onb.v).abs() < 1e-12);
+            assert!(onb.v.dot(&onb.w).abs() < 1e-12);
+            assert!(onb.w.dot(&onb.u).abs() < 1e-12);
+            assert!(onb.local(Vec3::with_z(1.)).approx_eq(&n.to_unit(), None));
+        }
+    }
+}
@@ -1,19 +1,72 @@
 use crate::{Point3, Vec3};
 
 /// A ray with the origin and direction.
+///
+/// The point at distance `t` along the ray is `origin + t * direction`.
 #[derive(Debug, Default)]
 pub struct Ray {
+    /// Point the ray starts from.
     pub origin: Point3,
+    /// Direction of the ray, not necessarily of unit length.
     pub direction: Vec3,
+    /// Index of the only color channel the ray carries, once split by a dispersive
+    /// material, `None` for all channels.
+    pub channel: Option<u8>,
 }
 
 impl Ray {
-    /// Create a new ray with the given origin and direction.
+    /// Create a new ray with the given origin and direction, carrying all channels.
     pub fn new(origin: Point3, direction: Vec3) -> Self {
-        Self { origin, direction }
+        Self {
+            origin,
+            direction,
+            channel: None,
+        }
+    }
+
+    /// Sets the color channel carried by the ray.
+    pub fn set_channel(mut self, channel: Option<u8>) -> Self {
+        self.channel = channel;
+        self
+    }
+
+    /// Returns the origin of the ray.
+    pub fn origin(&self) -> Point3 {
+        self.origin
+    }
+
+    /// Returns the direction of the ray, as given to `new`.
+    pub fn direction(&self) -> Vec3 {
+        self.direction
+    }
+
+    /// Returns the normalized direction of the ray.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ray_tracing_core::{ray::Ray, Point3, Vec3};
+    /// let ray = Ray::new(Point3::zero(), Vec3::new(0., 3., 4.));
+    /// assert!(ray.direction_unit().approx_eq(&Vec3::new(0., 0.6, 0.8), None));
+    /// ```
+    ///
+    /// # Note
+    ///
+    /// The unit direction is computed on each call (one `sqrt`) rather than cached,
+    /// since the fields are public and a cached value could go stale.
+    pub fn direction_unit(&self) -> Vec3 {
+        self.direction.to_unit()
     }
 
     /// Calculate the point at distance `t` along the ray.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ray_tracing_core::{ray::Ray, Point3, Vec3};
+    /// let ray = Ray::new(Point3::new(1., 0., 0.), Vec3::new(0., 2., 0.));
+    /// assert_eq!(ray.at(1.5), Point3::new(1., 3., 0.));
+    /// ```
     pub fn at(&self, t: f64) -> Point3 {
         self.origin + self.direction * t
     }
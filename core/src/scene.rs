@@ -0,0 +1,340 @@
+//! Load a scene (camera, materials and objects) from a JSON description.
+//!
+//! # Format
+//!
+//! ```json
+//! {
+//!     "camera": {
+//!         "aspect_ratio": 1.7777777777777777,
+//!         "image_width": 400,
+//!         "look_from": [13, 2, 3],
+//!         "look_at": [0, 0, 0]
+//!     },
+//!     "materials": {
+//!         "ground": { "type": "lambertian", "albedo": [0.5, 0.5, 0.5] },
+//!         "glass": { "type": "dielectric", "refraction_index": 1.5 }
+//!     },
+//!     "objects": [
+//!         { "type": "sphere", "center": [0, -1000, 0], "radius": 1000, "material": "ground" },
+//!         { "type": "sphere", "center": [0, 1, 0], "radius": 1, "material": "glass" }
+//!     ]
+//! }
+//! ```
+//!
+//! Every camera setting is optional and falls back to the `Camera::builder()` default.
+
+use std::{collections::HashMap, error::Error, fmt::Display, fs, io, path::Path, sync::Arc};
+
+use serde::Deserialize;
+
+use crate::prelude::*;
+
+/// A scene loaded from a description file, ready to render.
+pub struct Scene {
+    pub camera: Camera,
+    pub world: HittableList,
+}
+
+/// Error raised when loading a scene.
+#[derive(Debug)]
+pub enum SceneError {
+    /// The description file can't be read
+    Io(io::Error),
+    /// The description is not valid
+    Parse(serde_json::Error),
+    /// An object refers to a material which is not declared
+    UnknownMaterial(String),
+    /// The camera settings are not valid
+    Camera(CameraError),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SceneDesc {
+    #[serde(default)]
+    camera: CameraDesc,
+    #[serde(default)]
+    materials: HashMap<String, MaterialDesc>,
+    #[serde(default)]
+    objects: Vec<ObjectDesc>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct CameraDesc {
+    aspect_ratio: Option<f64>,
+    image_width: Option<u32>,
+    samples_per_pixel: Option<u32>,
+    max_depth: Option<u32>,
+    vfov: Option<f64>,
+    look_from: Option<[f64; 3]>,
+    look_at: Option<[f64; 3]>,
+    vup: Option<[f64; 3]>,
+    defocus_angle: Option<f64>,
+    focus_distance: Option<f64>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+enum MaterialDesc {
+    Lambertian {
+        albedo: [f64; 3],
+    },
+    Metal {
+        albedo: [f64; 3],
+        #[serde(default)]
+        fuzz: f64,
+    },
+    Conductor {
+        albedo: [f64; 3],
+        #[serde(default)]
+        fuzz: f64,
+    },
+    Dielectric {
+        refraction_index: f64,
+        tint: Option<[f64; 3]>,
+    },
+    Mix {
+        a: Box<MaterialDesc>,
+        b: Box<MaterialDesc>,
+        factor: f64,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+enum ObjectDesc {
+    Sphere {
+        center: [f64; 3],
+        radius: f64,
+        material: Option<String>,
+    },
+    Triangle {
+        a: [f64; 3],
+        b: [f64; 3],
+        c: [f64; 3],
+        material: Option<String>,
+    },
+}
+
+impl Scene {
+    /// Load a scene from a JSON description file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, SceneError> {
+        let json = fs::read_to_string(path)?;
+
+        Self::from_json(&json)
+    }
+
+    /// Load a scene from a JSON description.
+    pub fn from_json(json: &str) -> Result<Self, SceneError> {
+        let desc: SceneDesc = serde_json::from_str(json)?;
+
+        let materials = desc
+            .materials
+            .into_iter()
+            .map(|(name, material)| (name, material.into_material()))
+            .collect::<HashMap<_, _>>();
+
+        let mut world = HittableList::with_capacity(desc.objects.len());
+        for object in desc.objects {
+            world.add(object.into_hittable(&materials)?);
+        }
+
+        Ok(Self {
+            camera: desc.camera.into_camera()?,
+            world,
+        })
+    }
+
+    /// Render the scene.
+    pub fn render(self) -> Result<RenderStats, RenderError> {
+        let Self { mut camera, world } = self;
+
+        camera.render(Arc::new(world))
+    }
+}
+
+impl CameraDesc {
+    fn into_camera(self) -> Result<Camera, CameraError> {
+        let mut camera = Camera::builder();
+
+        if let Some(aspect_ratio) = self.aspect_ratio {
+            camera = camera.set_aspect_ratio(aspect_ratio);
+        }
+        if let Some(image_width) = self.image_width {
+            camera = camera.set_image_width(image_width);
+        }
+        if let Some(samples_per_pixel) = self.samples_per_pixel {
+            camera = camera.set_samples_per_pixel(samples_per_pixel);
+        }
+        if let Some(max_depth) = self.max_depth {
+            camera = camera.set_max_depth(max_depth);
+        }
+        if let Some(vfov) = self.vfov {
+            camera = camera.set_vertical_view_angle(vfov);
+        }
+        if let Some(look_from) = self.look_from {
+            camera = camera.set_look_from(look_from.into());
+        }
+        if let Some(look_at) = self.look_at {
+            camera = camera.set_look_at(look_at.into());
+        }
+        if let Some(vup) = self.vup {
+            camera = camera.set_vup(vup.into());
+        }
+        if let Some(defocus_angle) = self.defocus_angle {
+            camera = camera.set_defocus_angle(defocus_angle);
+        }
+        if let Some(focus_distance) = self.focus_distance {
+            camera = camera.set_focus_distance(focus_distance);
+        }
+
+        camera.try_build()
+    }
+}
+
+impl MaterialDesc {
+    fn into_material(self) -> Arc<dyn Material> {
+        match self {
+            Self::Lambertian { albedo } => Arc::new(Lambertian::new(albedo.into())),
+            Self::Metal { albedo, fuzz } => Arc::new(Metal::new(albedo.into(), fuzz)),
+            Self::Conductor { albedo, fuzz } => Arc::new(Conductor::new(albedo.into(), fuzz)),
+            Self::Dielectric {
+                refraction_index,
+                tint,
+            } => Arc::new(Dielectric::new_colored(
+                refraction_index,
+                tint.map_or(Color::one(), Color::from),
+            )),
+            Self::Mix { a, b, factor } => {
+                Arc::new(Mix::new(a.into_material(), b.into_material(), factor))
+            }
+        }
+    }
+}
+
+impl ObjectDesc {
+    fn into_hittable(
+        self,
+        materials: &HashMap<String, Arc<dyn Material>>,
+    ) -> Result<Arc<dyn Hittable>, SceneError> {
+        let find_material = |name: Option<String>| {
+            name.map(|name| {
+                materials
+                    .get(&name)
+                    .cloned()
+                    .ok_or(SceneError::UnknownMaterial(name))
+            })
+            .transpose()
+        };
+
+        match self {
+            Self::Sphere {
+                center,
+                radius,
+                material,
+            } => Ok(Arc::new(Sphere::new(
+                center.into(),
+                radius,
+                find_material(material)?,
+            ))),
+            Self::Triangle { a, b, c, material } => Ok(Arc::new(Triangle::new(
+                a.into(),
+                b.into(),
+                c.into(),
+                find_material(material)?,
+            ))),
+        }
+    }
+}
+
+impl Display for SceneError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to read the scene: {err}"),
+            Self::Parse(err) => write!(f, "invalid scene description: {err}"),
+            Self::UnknownMaterial(name) => write!(f, "unknown material: {name}"),
+            Self::Camera(err) => write!(f, "invalid camera: {err}"),
+        }
+    }
+}
+
+impl Error for SceneError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Parse(err) => Some(err),
+            Self::UnknownMaterial(_) => None,
+            Self::Camera(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for SceneError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<CameraError> for SceneError {
+    fn from(err: CameraError) -> Self {
+        Self::Camera(err)
+    }
+}
+
+impl From<serde_json::Error> for SceneError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Parse(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scene_from_json_should_work() {
+        let scene = Scene::from_json(
+            r#"{
+                "camera": { "image_width": 10 },
+                "materials": {
+                    "ground": { "type": "lambertian", "albedo": [0.5, 0.5, 0.5] },
+                    "coat": {
+                        "type": "mix",
+                        "a": { "type": "lambertian", "albedo": [0.8, 0.1, 0.1] },
+                        "b": { "type": "metal", "albedo": [1, 1, 1] },
+                        "factor": 0.2
+                    }
+                },
+                "objects": [
+                    { "type": "sphere", "center": [0, -100, 0], "radius": 100, "material": "ground" },
+                    { "type": "sphere", "center": [0, 1, 0], "radius": 1, "material": "coat" },
+                    { "type": "sphere", "center": [2, 1, 0], "radius": 1 },
+                    { "type": "triangle", "a": [0, 0, 0], "b": [1, 0, 0], "c": [0, 1, 0] }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(scene.world.len(), 4);
+    }
+
+    #[test]
+    fn scene_from_json_should_reject_unknown_material() {
+        let result = Scene::from_json(
+            r#"{ "objects": [{ "type": "sphere", "center": [0, 0, 0], "radius": 1, "material": "gold" }] }"#,
+        );
+
+        assert!(matches!(result, Err(SceneError::UnknownMaterial(name)) if name == "gold"));
+    }
+
+    #[test]
+    fn scene_from_json_should_reject_invalid_camera() {
+        let result = Scene::from_json(r#"{ "camera": { "image_width": 0 } }"#);
+
+        assert!(matches!(
+            result,
+            Err(SceneError::Camera(CameraError::ZeroImageWidth))
+        ));
+    }
+}
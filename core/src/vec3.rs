@@ -7,7 +7,10 @@ use std::{
 use crate::common;
 
 /// Vector with three components.
+///
+/// With the `serde` feature it is (de)serialized as a map, like `{"x": 1, "y": 2, "z": 3}`.
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vec3 {
     /// X component.
     pub x: f64,
@@ -101,7 +104,7 @@ impl Vec3 {
     /// Like `random_unit_vector()` but only for two dimensions.
     pub fn random_in_unit_disk() -> Self {
         loop {
-            let p = Vec3::with_xy(common::random(), common::random());
+            let p = Vec3::with_xy(common::random_range(-1., 1.), common::random_range(-1., 1.));
 
             if p.length_squared() < 1. {
                 return p;
@@ -109,6 +112,59 @@ impl Vec3 {
         }
     }
 
+    /// Like `random_in_unit_disk()` but inside the regular polygon with `sides` vertices
+    /// on the unit circle, the first vertex at (0, 1).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ray_tracing_core::Vec3;
+    /// let p = Vec3::random_in_unit_polygon(6);
+    /// assert!(p.length() <= 1.);
+    /// assert_eq!(p.z, 0.);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sides < 3`.
+    pub fn random_in_unit_polygon(sides: u32) -> Self {
+        assert!(
+            sides >= 3,
+            "A polygon has at least 3 sides, but got {sides}"
+        );
+
+        // Pick one of the triangles fanning out from the center, then a uniform point in it.
+        let sector = std::f64::consts::TAU / sides as f64;
+        let k = ((common::random() * sides as f64) as u32).min(sides - 1) as f64;
+        let vertex = |angle: f64| Vec3::with_xy(-angle.sin(), angle.cos());
+        let (a, b) = (vertex(k * sector), vertex((k + 1.) * sector));
+
+        let (r1, r2) = (common::random(), common::random());
+        let (r1, r2) = if r1 + r2 > 1. {
+            (1. - r1, 1. - r2)
+        } else {
+            (r1, r2)
+        };
+
+        r1 * a + r2 * b
+    }
+
+    /// Generate a random unit vector around the z axis, with a density proportional to
+    /// the cosine of its angle to the axis, that is `cos(theta) / π`.
+    ///
+    /// Use `Onb::local` to orient it around a surface normal.
+    pub fn random_cosine_direction() -> Self {
+        let r1 = common::random();
+        let r2 = common::random();
+
+        let phi = std::f64::consts::TAU * r1;
+        let x = phi.cos() * r2.sqrt();
+        let y = phi.sin() * r2.sqrt();
+        let z = (1. - r2).sqrt();
+
+        Self::new(x, y, z)
+    }
+
     /// Take the dot product of the surface normal and our random vector to determine
     /// if it's in the correct hemisphere. If the dot product is positive, then the vector
     /// is in the correct hemisphere. If the dot product is negative,
@@ -136,6 +192,23 @@ impl Vec3 {
         self.x.abs() < epsilon && self.y.abs() < epsilon && self.z.abs() < epsilon
     }
 
+    /// Check if two vectors are relatively equal within a given epsilon, component by
+    /// component. (See `common::relative_eq`)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ray_tracing_core::Vec3;
+    /// let v = Vec3::new(0.1, 0.2, 0.3) + Vec3::new(0.2, 0.1, 0.);
+    /// assert_ne!(v, Vec3::new(0.3, 0.3, 0.3));
+    /// assert!(v.approx_eq(&Vec3::new(0.3, 0.3, 0.3), None));
+    /// ```
+    pub fn approx_eq(&self, other: &Self, epsilon: Option<f64>) -> bool {
+        common::relative_eq(self.x, other.x, epsilon)
+            && common::relative_eq(self.y, other.y, epsilon)
+            && common::relative_eq(self.z, other.z, epsilon)
+    }
+
     /// Creates a new vector with all components set to the given value.
     ///
     /// # Examples
@@ -247,6 +320,26 @@ impl Vec3 {
         }
     }
 
+    /// Returns the component at `index` (0 for x, 1 for y, 2 for z), or `None` if out of
+    /// range, unlike indexing which panics.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ray_tracing_core::Vec3;
+    /// let v = Vec3::new(1., 2., 3.);
+    /// assert_eq!(v.get(1), Some(2.));
+    /// assert_eq!(v.get(3), None);
+    /// ```
+    pub fn get(&self, index: u8) -> Option<f64> {
+        match index {
+            0 => Some(self.x),
+            1 => Some(self.y),
+            2 => Some(self.z),
+            _ => None,
+        }
+    }
+
     /// Returns the dot product of two vectors. (Be like: v1 * v2)
     ///
     /// Tip: v1.x * v2.x + v1.y * v2.y + v1.z * v2.z
@@ -296,10 +389,11 @@ impl Vec3 {
     /// # Examples
     ///
     /// ```rust
-    /// # use ray_tracing_core::Vec3;
+    /// # use ray_tracing_core::{common, Vec3};
     /// let v = Vec3::new(1., 2., 3.);
     /// let u = v.to_unit();
-    /// assert_eq!(u.length(), 1.);
+    /// assert!(common::relative_eq(u.length(), 1., None));
+    /// assert!((u * v.length()).approx_eq(&v, None));
     /// ```
     ///
     /// # Note
@@ -322,6 +416,163 @@ impl Vec3 {
     pub fn length(&self) -> f64 {
         self.length_squared().sqrt()
     }
+
+    /// Returns the distance between two points.
+    ///
+    /// Tip: (v1 - v2).length()
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ray_tracing_core::Point3;
+    /// let d = Point3::new(1., 2., 3.).distance(&Point3::new(4., 6., 3.));
+    /// assert_eq!(d, 5.);
+    /// ```
+    pub fn distance(&self, other: &Self) -> f64 {
+        (*self - *other).length()
+    }
+
+    /// Returns the projection of the vector onto the direction of `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ray_tracing_core::Vec3;
+    /// let v = Vec3::new(1., 2., 3.).project_onto(&Vec3::with_y(5.));
+    /// assert_eq!(v, Vec3::with_y(2.));
+    /// ```
+    ///
+    /// # Note
+    ///
+    /// `other` must not be the zero vector.
+    pub fn project_onto(&self, other: &Self) -> Self {
+        self.dot(other) / other.length_squared() * *other
+    }
+
+    /// Returns the component of the vector orthogonal to `other`, e.g. a direction
+    /// without its normal component.
+    ///
+    /// Tip: v - v.project_onto(other)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ray_tracing_core::Vec3;
+    /// let v = Vec3::new(1., 2., 3.).reject_from(&Vec3::with_y(5.));
+    /// assert_eq!(v, Vec3::new(1., 0., 3.));
+    /// ```
+    ///
+    /// # Note
+    ///
+    /// `other` must not be the zero vector.
+    pub fn reject_from(&self, other: &Self) -> Self {
+        *self - self.project_onto(other)
+    }
+
+    /// Returns the vector mirrored by a surface with the unit `normal`, like `reflect()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ray_tracing_core::Vec3;
+    /// let v = Vec3::new(1., -1., 0.).reflect_around(&Vec3::with_y(1.));
+    /// assert_eq!(v, Vec3::new(1., 1., 0.));
+    /// ```
+    pub fn reflect_around(&self, normal: &Self) -> Self {
+        reflect(self, normal)
+    }
+
+    /// Returns the spherical angles `(theta, phi)` of the direction of the vector.
+    ///
+    /// * `theta`: angle from Y=-1 up to Y=+1, in [0, π].
+    /// * `phi`: angle around the Y axis from X=-1 (to Z=+1, then X=+1, then Z=-1),
+    ///   in [0, 2π].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ray_tracing_core::{common, Vec3};
+    /// use std::f64::consts::PI;
+    ///
+    /// let (theta, phi) = Vec3::new(0., 0., 2.).to_spherical();
+    /// assert!(common::relative_eq(theta, PI / 2., None));
+    /// assert!(common::relative_eq(phi, PI / 2., None));
+    /// ```
+    pub fn to_spherical(&self) -> (f64, f64) {
+        let unit = self.to_unit();
+        let theta = (-unit.y).clamp(-1., 1.).acos();
+        let phi = (-unit.z).atan2(unit.x) + std::f64::consts::PI;
+
+        (theta, phi)
+    }
+
+    /// Returns the unit vector with the spherical angles `(theta, phi)`, the inverse of
+    /// `to_spherical()`.
+    pub fn from_spherical(theta: f64, phi: f64) -> Self {
+        Self::new(
+            -phi.cos() * theta.sin(),
+            -theta.cos(),
+            phi.sin() * theta.sin(),
+        )
+    }
+
+    /// Returns the component-wise minimum of two vectors.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ray_tracing_core::Vec3;
+    /// let v = Vec3::new(1., 5., -3.).min(&Vec3::new(2., 4., -6.));
+    /// assert_eq!(v, Vec3::new(1., 4., -6.));
+    /// ```
+    pub fn min(&self, other: &Self) -> Self {
+        Self::new(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+        )
+    }
+
+    /// Returns the component-wise maximum of two vectors.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ray_tracing_core::Vec3;
+    /// let v = Vec3::new(1., 5., -3.).max(&Vec3::new(2., 4., -6.));
+    /// assert_eq!(v, Vec3::new(2., 5., -3.));
+    /// ```
+    pub fn max(&self, other: &Self) -> Self {
+        Self::new(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+        )
+    }
+
+    /// Returns the vector with the absolute value of each component.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ray_tracing_core::Vec3;
+    /// assert_eq!(Vec3::new(-1., 2., -0.5).abs(), Vec3::new(1., 2., 0.5));
+    /// ```
+    pub fn abs(&self) -> Self {
+        Self::new(self.x.abs(), self.y.abs(), self.z.abs())
+    }
+
+    /// Returns the vector with each component rounded down.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ray_tracing_core::Vec3;
+    /// assert_eq!(Vec3::new(-1.5, 2.7, 3.).floor(), Vec3::new(-2., 2., 3.));
+    /// ```
+    pub fn floor(&self) -> Self {
+        Self::new(self.x.floor(), self.y.floor(), self.z.floor())
+    }
 }
 
 /// Returns the dot product of two vectors.
@@ -378,6 +629,40 @@ impl<'a> Iterator for Vec3Iter<'a> {
     }
 }
 
+impl<'a> IntoIterator for &'a Vec3 {
+    type Item = f64;
+    type IntoIter = Vec3Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Collects exactly three components into a vector, e.g. after mapping over them.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ray_tracing_core::Vec3;
+/// let v = Vec3::new(-1., 4., -9.);
+/// let w: Vec3 = v.iter().map(|c| c.abs().sqrt()).collect();
+/// assert_eq!(w, Vec3::new(1., 2., 3.));
+/// ```
+///
+/// # Panics
+///
+/// Panics if the iterator doesn't yield exactly three components.
+impl FromIterator<f64> for Vec3 {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut iter = iter.into_iter();
+        let mut next = || iter.next().expect("Expected 3 components, but got fewer");
+        let v = Vec3::new(next(), next(), next());
+        assert!(iter.next().is_none(), "Expected 3 components, but got more");
+
+        v
+    }
+}
+
 impl Index<u8> for Vec3 {
     type Output = f64;
 
@@ -413,6 +698,13 @@ impl From<Vec3> for (f64, f64, f64) {
     }
 }
 
+// Converts an array, like [x, y, z], to Vec3.
+impl From<[f64; 3]> for Vec3 {
+    fn from([x, y, z]: [f64; 3]) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
 // -v
 impl Neg for Vec3 {
     type Output = Self;
@@ -707,7 +999,80 @@ mod tests {
     fn vec3_to_unit_should_work() {
         let v = Vec3::new(1., 2., 3.);
 
-        assert_eq!(v.to_unit().length(), 1.);
+        assert!(common::relative_eq(v.to_unit().length(), 1., None));
+        assert!(v.to_unit().approx_eq(&(v / 14f64.sqrt()), None));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn vec3_serde_should_work() {
+        let v = Vec3::new(1., 2.5, -3.);
+        let json = serde_json::to_string(&v).unwrap();
+
+        assert_eq!(json, r#"{"x":1.0,"y":2.5,"z":-3.0}"#);
+        assert_eq!(serde_json::from_str::<Vec3>(&json).unwrap(), v);
+    }
+
+    #[test]
+    fn vec3_approx_eq_should_work() {
+        let v = Vec3::new(1., 0., -3.);
+
+        assert!(v.approx_eq(&Vec3::new(1.000000001, 0., -3.), None));
+        assert!(!v.approx_eq(&Vec3::new(1.00000002, 0., -3.), None));
+        assert!(!v.approx_eq(&Vec3::new(1., 1e-6, -3.), None));
+        assert!(v.approx_eq(&Vec3::new(1.001, 0., -3.), Some(1e-2)));
+    }
+
+    #[test]
+    fn vec3_random_in_unit_disk_should_cover_the_disk() {
+        let points = (0..1000).map(|_| Vec3::random_in_unit_disk());
+
+        assert!(points.clone().all(|p| p.length() < 1. && p.z == 0.));
+        assert!(points.clone().any(|p| p.x < 0.));
+        assert!(points.clone().any(|p| p.y < 0.));
+    }
+
+    #[test]
+    fn vec3_random_in_unit_polygon_should_stay_inside() {
+        // Square with vertices on the axes: |x| + |y| <= 1
+        for _ in 0..1000 {
+            let p = Vec3::random_in_unit_polygon(4);
+
+            assert!(p.x.abs() + p.y.abs() <= 1. + 1e-12);
+        }
+    }
+
+    #[test]
+    fn vec3_random_cosine_direction_should_work() {
+        let directions: Vec<_> = (0..10000)
+            .map(|_| Vec3::random_cosine_direction())
+            .collect();
+
+        assert!(
+            directions
+                .iter()
+                .all(|d| d.z >= 0. && common::relative_eq(d.length(), 1., None))
+        );
+        // The mean cosine is 2/3 for a cosine-weighted density.
+        let mean_z = directions.iter().map(|d| d.z).sum::<f64>() / directions.len() as f64;
+        assert!((mean_z - 2. / 3.).abs() < 0.02);
+    }
+
+    #[test]
+    fn vec3_spherical_should_round_trip() {
+        for v in [
+            Vec3::new(1., 2., 3.),
+            Vec3::new(-0.5, -0.2, 0.1),
+            Vec3::new(0.3, 0.4, -2.),
+            Vec3::with_x(-1.),
+        ] {
+            let (theta, phi) = v.to_spherical();
+
+            // Compare by distance, some components are (almost) zero.
+            assert!((Vec3::from_spherical(theta, phi) - v.to_unit()).length() < 1e-12);
+        }
+
+        assert!((Vec3::from_spherical(0., 1.) - Vec3::with_y(-1.)).length() < 1e-12);
     }
 
     #[test]
@@ -718,4 +1083,22 @@ mod tests {
         assert_eq!(v[1], 2.);
         assert_eq!(v[2], 3.);
     }
+
+    #[test]
+    fn vec3_into_iter_and_collect_should_work() {
+        let v = Vec3::new(1., 2., 3.);
+
+        let mut sum = 0.;
+        for c in &v {
+            sum += c;
+        }
+        assert_eq!(sum, 6.);
+        assert_eq!(v.iter().map(|c| 2. * c).collect::<Vec3>(), 2. * v);
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected 3 components, but got more")]
+    fn vec3_collect_should_panic_on_extra_components() {
+        let _ = [1., 2., 3., 4.].into_iter().collect::<Vec3>();
+    }
 }